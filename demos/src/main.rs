@@ -1,6 +1,19 @@
+mod make_more;
 mod micro_grad;
 
 fn main() {
+    // cargo run -- --profile
+    if std::env::args().any(|arg| arg == "--profile") {
+        micro_grad::profile::_profile();
+        return;
+    }
+
     // micro_grad::_run_all_examples();
     micro_grad::_binary_classifier();
+    // micro_grad::checkpoint_class::_checkpoint_resume();
+    // micro_grad::multi_class::_multi_class_classifier();
+    // micro_grad::parallel_class::_parallel_binary_classifier();
+    // micro_grad::tape_bench::_tape_benchmark();
+    // make_more::_bigram_names();
+    // make_more::_char_mlp_names();
 }
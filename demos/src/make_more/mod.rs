@@ -0,0 +1,87 @@
+use make_more::{
+    build_dataset, report, split_words, Bigram, CharMLP, LanguageModel, SamplingOptions, Vocab,
+};
+use rand::{rngs::StdRng, Rng, SeedableRng};
+use std::fs;
+
+/// reads the names from data/names.txt, one name per line.
+#[allow(dead_code)]
+fn read_names() -> Vec<String> {
+    fs::read_to_string("./data/names.txt")
+        .expect("expecting the names at ./data/names.txt")
+        .lines()
+        .map(|l| l.trim().to_lowercase())
+        .filter(|l| !l.is_empty())
+        .collect()
+}
+
+/// counts the character pairs of the train names, evaluates it on each split
+/// and generates new names with different sampling options.
+pub fn _bigram_names() {
+    let words = read_names();
+    let vocab = Vocab::from_words(&words);
+    let splits = split_words(&words, 0.8, 0.1, 42);
+    let model = Bigram::new(vocab, &splits.train, 1.0);
+
+    println!("\nevaluation of the bigram model:");
+    print!("{}", report(&model, &splits));
+
+    let mut rng = StdRng::seed_from_u64(2147483647);
+    println!("\nnames generated by the bigram model:");
+    println!("{:?}", model.generate(10, 20, &mut rng));
+
+    let options = SamplingOptions {
+        temperature: 0.7,
+        top_k: Some(5),
+        ..Default::default()
+    };
+    println!("\nwith temperature 0.7 and top_k 5:");
+    println!("{:?}", model.generate_custom(10, 20, &options, &mut rng));
+}
+
+/// trains the CharMLP on random mini batches of the train names, evaluates it on each split
+/// and generates new names.
+pub fn _char_mlp_names() {
+    let words = read_names();
+    let vocab = Vocab::from_words(&words);
+    let splits = split_words(&words, 0.8, 0.1, 42);
+    let block_size = 3;
+    let (contexts, targets) = build_dataset(&splits.train, &vocab, block_size);
+    let mut model = CharMLP::new(vocab, block_size, 4, 32);
+
+    let mut rng = StdRng::seed_from_u64(42);
+    let batch_size = 32;
+    let steps = 500;
+    println!("\ntraining the model:");
+    for k in 0..steps {
+        let batch: Vec<usize> = (0..batch_size)
+            .map(|_| rng.gen_range(0..contexts.len()))
+            .collect();
+        let batch_contexts: Vec<Vec<usize>> = batch.iter().map(|&i| contexts[i].clone()).collect();
+        let batch_targets: Vec<usize> = batch.iter().map(|&i| targets[i]).collect();
+        let learning_rate = if k < steps / 2 { 0.1 } else { 0.01 };
+        let loss = match model.train_step(&batch_contexts, &batch_targets, learning_rate) {
+            Ok(loss) => loss,
+            Err(err) => {
+                dbg!(err);
+                return;
+            }
+        };
+        if k % 50 == 0 {
+            println!("step:{k}, loss:{loss}");
+        }
+    }
+
+    println!("\nevaluation of the CharMLP:");
+    print!("{}", report(&model, &splits));
+
+    println!("\nnames generated by the CharMLP:");
+    println!("{:?}", model.generate(10, 20, &mut rng));
+
+    let options = SamplingOptions {
+        top_p: Some(0.9),
+        ..Default::default()
+    };
+    println!("\nwith top_p 0.9:");
+    println!("{:?}", model.generate_custom(10, 20, &options, &mut rng));
+}
@@ -1,9 +1,15 @@
-use crate::micro_grad::utils::{_scatter_plot, make_moons};
-use micrograd::{MVal, MLP};
+use crate::micro_grad::history::TrainingHistory;
+use crate::micro_grad::utils::{
+    _decision_boundary_plot, _scatter_plot, _scatter_plot_custom, make_moons, train_val_split,
+    ColorMode, PlotOptions,
+};
+use micrograd::{metrics, optim::EarlyStopping, MVal, MLP};
 use plotters::prelude::LogScalable;
 
 pub fn _binary_classifier() {
-    let (inps, outs) = make_moons(200, 0.1);
+    // 200 samples for training and 50 samples for validation.
+    let (all_inps, all_outs) = make_moons(250, 0.1);
+    let ((inps, outs), (val_inps, val_outs)) = train_val_split(&all_inps, &all_outs, 0.2);
     // println!("Data: {:?}\n Labels: {:?}", data,labels);
     if let Err(err) = _scatter_plot(
         &inps,
@@ -20,18 +26,28 @@ pub fn _binary_classifier() {
     // third layer will have 1 neuron with 16 inputs. third layer will have single output.
     let mut model = MLP::new(2, vec![16, 16, 1]); // 2-layer neural network
 
+    let batch: Vec<Vec<f64>> = inps.rows().into_iter().map(|row| row.to_vec()).collect();
+    let val_batch: Vec<Vec<f64>> = val_inps
+        .rows()
+        .into_iter()
+        .map(|row| row.to_vec())
+        .collect();
+    // the validation loss is evaluated every eval_every steps and the training is stopped
+    // when it is not improving for 4 evaluations in a row.
+    let eval_every = 5;
+    let mut early_stopping = EarlyStopping::new(4, 1e-4);
+    let params = model.parameters();
+    let mut history = TrainingHistory::new();
     println!("\ntraining the model:");
     for k in 0..100 {
-        let mut index = 0;
         let mut loss = MVal::new(0.0);
-        let mut accuracy = 0.0;
-        for row in inps.rows() {
-            let v = row.to_vec();
-            // forward the model to get the prediction
+        let mut scores = vec![];
+        // forward the whole batch of training samples.
+        let batch_outs = model.forward_batch(&batch);
+        for (index, pre) in batch_outs.iter().enumerate() {
             // ypre is expected to be approximately equal to -1 or 1. This is what we are training the model for.
-            let pre = model
-                .forward(v)
-                .get(0)
+            let pre = pre
+                .first()
                 .expect("expecting single output since the last layer has single neuron")
                 .clone();
             // println!("pred:{pre}");
@@ -42,18 +58,11 @@ pub fn _binary_classifier() {
                 .expect("expecting the output label for each input");
             // svm "max-margin" loss. loss will increase when ouput and prediction is not matching
             loss = loss + (1.0 - pre.clone() * out.as_f64());
-
-            // accuracy will be high when both output and prediction has the same sign.
-            accuracy = accuracy
-                + (if pre.get() * (*out).as_f64() > 0.0 {
-                    1.0
-                } else {
-                    0.0
-                });
-            index += 1;
+            scores.push(pre.get());
         }
-        let mut avg_loss = loss.clone() / index.as_f64();
-        let avg_accr = accuracy * 100.0 / index.as_f64();
+        let mut avg_loss = loss.clone() / batch_outs.len().as_f64();
+        // accuracy will be high when both output and prediction has the same sign.
+        let avg_accr = metrics::accuracy(&metrics::binary_labels(&scores, 0.0), &outs) * 100.0;
         println!("step:{k}, loss:{avg_loss}, accuracy:{avg_accr}%");
 
         // todo: implement the L2 normalisation
@@ -61,46 +70,104 @@ pub fn _binary_classifier() {
 
         // optimisation
         avg_loss.zero_grad();
-        avg_loss.backward();
+        if let Err(err) = avg_loss.backward() {
+            dbg!(err);
+            return;
+        }
 
         let learning_rate = 1.0 - (0.9 * k.as_f64() / 100.0);
         for p in model.parameters() {
             p.set(p.get() - MVal::new(learning_rate * p.grad()));
         }
+        history.record(k, avg_loss.get(), avg_accr, learning_rate);
+
+        if k % eval_every == 0 {
+            // same loss as the training but computed without building the MVal graph.
+            let val_loss = val_batch
+                .iter()
+                .zip(&val_outs)
+                .map(|(inp, out)| 1.0 - model.predict(inp)[0] * out)
+                .sum::<f64>()
+                / val_batch.len().as_f64();
+            println!("step:{k}, validation loss:{val_loss}");
+            if early_stopping.update(k, val_loss, &params) {
+                println!("stopping early since the validation loss is not improving");
+                break;
+            }
+        }
+    }
+    early_stopping.restore_best(&params);
+    println!(
+        "restored the best weights of step:{} with validation loss:{}",
+        early_stopping.best_step(),
+        early_stopping.best_loss()
+    );
+
+    if let Err(err) = history.save_csv("./images/training_history.csv") {
+        dbg!(err);
+    }
+    if let Err(err) = history.plot("Training History", "./images/training_history.png") {
+        dbg!(err);
     }
 
     // make prediction for newly generated data
     let (inps, outs) = make_moons(150, 0.2);
     println!("\nmaking the predictions:");
-    let mut index = 0;
     let mut preds = vec![];
-    for row in inps.rows() {
+    for (index, row) in inps.rows().into_iter().enumerate() {
         let v = row.to_vec();
-        // forward the model to get the prediction
+        // no need to build the MVal graph since we are not training here.
         // ypre is expected to be approximately equal to -1 or 1. This is what we are training the model for.
-        let pre = model
-            .forward(v)
-            .get(0)
-            .expect("expecting single output since the last layer has single neuron")
-            .clone();
-        preds.push(pre.get());
+        let pre = *model
+            .predict(&v)
+            .first()
+            .expect("expecting single output since the last layer has single neuron");
+        preds.push(pre);
 
         let out = outs
             .get(index)
             .expect("expecting the output label for each input");
 
         println!("pred:{pre}, out:{out}");
-
-        index += 1;
     }
 
-    if let Err(err) = _scatter_plot(
+    let pred_labels = metrics::binary_labels(&preds, 0.0);
+    let report = metrics::precision_recall_f1(&pred_labels, &outs, 1.0);
+    println!(
+        "\naccuracy:{}, precision:{}, recall:{}, f1:{}, roc_auc:{}",
+        metrics::accuracy(&pred_labels, &outs),
+        report.precision,
+        report.recall,
+        report.f1,
+        metrics::roc_auc(&preds, &outs, 1.0),
+    );
+
+    // predictions are continuous scores between -1 and 1.
+    let pred_options = PlotOptions {
+        color_mode: ColorMode::Continuous,
+        ..Default::default()
+    };
+    if let Err(err) = _scatter_plot_custom(
         &inps,
         &preds,
         "Prediction Sample",
         "./images/prediction.png",
+        &pred_options,
     ) {
         dbg!(err);
         return;
     }
+
+    // visualise what the model has learned over the whole data extent.
+    if let Err(err) = _decision_boundary_plot(
+        &model,
+        &inps,
+        &outs,
+        100,
+        "Decision Boundary",
+        "./images/decision_boundary.png",
+        &PlotOptions::default(),
+    ) {
+        dbg!(err);
+    }
 }
@@ -0,0 +1,99 @@
+use crate::micro_grad::utils::make_moons;
+use micrograd::{
+    checkpoint::{ChaCha8Rng, Checkpoint},
+    optim::{LinearDecay, Sgd},
+    MVal, MLP,
+};
+use plotters::prelude::LogScalable;
+use rand::{Rng, SeedableRng};
+
+/// trains the binary classifier on random mini batches and saves a checkpoint in the middle.
+/// Then the training is resumed from the checkpoint on a new model and
+/// the final weights are compared with the weights of the uninterrupted training.
+pub fn _checkpoint_resume() {
+    let (inps, outs) = make_moons(200, 0.1);
+    let samples: Vec<Vec<f64>> = inps.rows().into_iter().map(|row| row.to_vec()).collect();
+    let steps = 100;
+    let batch_size = 32;
+    let checkpoint_step = 49;
+    let checkpoint_path = "./images/checkpoint.txt";
+
+    // one step of training on a random mini batch with svm "max-margin" loss.
+    let train_step = |model: &mut MLP,
+                      optimizer: &mut Sgd,
+                      scheduler: &mut LinearDecay,
+                      rng: &mut ChaCha8Rng,
+                      step: usize| {
+        let mut loss = MVal::new(0.0);
+        for _ in 0..batch_size {
+            let i = rng.gen_range(0..samples.len());
+            let pre = model.forward(samples[i].clone())[0].clone();
+            loss = loss + (1.0 - pre * outs[i]).relu();
+        }
+        let mut avg_loss = loss / batch_size.as_f64();
+        println!(
+            "step:{step}, loss:{}, learning rate:{}",
+            avg_loss.get(),
+            scheduler.learning_rate()
+        );
+
+        avg_loss.zero_grad();
+        if let Err(err) = avg_loss.backward() {
+            dbg!(err);
+            return;
+        }
+        optimizer.step(&model.parameters(), scheduler.learning_rate());
+        scheduler.step();
+    };
+
+    let mut model = MLP::new(2, vec![16, 16, 1]);
+    let mut optimizer = Sgd::new(0.9);
+    let mut scheduler = LinearDecay::new(0.1, 0.01, steps);
+    let mut rng = ChaCha8Rng::seed_from_u64(42);
+    println!("\ntraining the model:");
+    for step in 0..steps {
+        train_step(&mut model, &mut optimizer, &mut scheduler, &mut rng, step);
+        if step == checkpoint_step {
+            let epoch = (step + 1) * batch_size / samples.len();
+            let checkpoint = Checkpoint::new(&mut model, &optimizer, &scheduler, &rng, step, epoch);
+            if let Err(err) = checkpoint.save(checkpoint_path) {
+                dbg!(err);
+                return;
+            }
+        }
+    }
+
+    // new model with different initial weights resumed from the checkpoint.
+    let mut resumed = MLP::new(2, vec![16, 16, 1]);
+    let mut optimizer = Sgd::new(0.9);
+    let mut scheduler = LinearDecay::new(0.1, 0.01, steps);
+    let checkpoint = match Checkpoint::load(checkpoint_path) {
+        Ok(checkpoint) => checkpoint,
+        Err(err) => {
+            dbg!(err);
+            return;
+        }
+    };
+    let mut rng = match checkpoint.restore(&mut resumed, &mut optimizer, &mut scheduler) {
+        Ok(rng) => rng,
+        Err(err) => {
+            dbg!(err);
+            return;
+        }
+    };
+    println!(
+        "\nresuming the training from step:{}, epoch:{}",
+        checkpoint.step(),
+        checkpoint.epoch()
+    );
+    for step in checkpoint.step() + 1..steps {
+        train_step(&mut resumed, &mut optimizer, &mut scheduler, &mut rng, step);
+    }
+
+    let identical = model
+        .parameters()
+        .iter()
+        .zip(resumed.parameters())
+        .all(|(a, b)| a.get().to_bits() == b.get().to_bits());
+    println!("\nresumed weights are bit identical to the uninterrupted training: {identical}");
+}
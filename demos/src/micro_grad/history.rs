@@ -0,0 +1,122 @@
+use crate::micro_grad::utils::is_svg;
+use plotters::{coord::Shift, prelude::*};
+use std::{fs::File, io::Write};
+
+/// metrics recorded for a single training step.
+#[derive(Debug, Clone)]
+pub struct HistoryEntry {
+    pub step: usize,
+    pub loss: f64,
+    pub accuracy: f64,
+    pub learning_rate: f64,
+}
+
+/// Collects the per step loss, accuracy and learning rate of a training run
+/// so that they can be plotted or exported after the training.
+#[derive(Debug, Default)]
+pub struct TrainingHistory {
+    entries: Vec<HistoryEntry>,
+}
+
+impl TrainingHistory {
+    pub fn new() -> Self {
+        Self { entries: vec![] }
+    }
+
+    /// records the metrics of a training step.
+    /// accuracy is expected to be in percentage(0 to 100).
+    pub fn record(&mut self, step: usize, loss: f64, accuracy: f64, learning_rate: f64) {
+        self.entries.push(HistoryEntry {
+            step,
+            loss,
+            accuracy,
+            learning_rate,
+        });
+    }
+
+    /// writes the raw history as `step,loss,accuracy,learning_rate` rows with a header line.
+    pub fn save_csv(&self, file_path: &str) -> std::io::Result<()> {
+        let mut file = File::create(file_path)?;
+        writeln!(file, "step,loss,accuracy,learning_rate")?;
+        for e in &self.entries {
+            writeln!(
+                file,
+                "{},{},{},{}",
+                e.step, e.loss, e.accuracy, e.learning_rate
+            )?;
+        }
+        Ok(())
+    }
+
+    /// renders the loss, accuracy and learning rate line charts one below the other.
+    /// SVG is used when the file_path ends with `.svg` otherwise PNG bitmap is used.
+    pub fn plot(&self, title: &str, file_path: &str) -> Result<(), Box<dyn std::error::Error>> {
+        if is_svg(file_path) {
+            let root_area = SVGBackend::new(file_path, (1024, 1152)).into_drawing_area();
+            self.draw(root_area, title)
+        } else {
+            let root_area = BitMapBackend::new(file_path, (1024, 1152)).into_drawing_area();
+            self.draw(root_area, title)
+        }
+    }
+
+    fn draw<DB: DrawingBackend>(
+        &self,
+        root_area: DrawingArea<DB, Shift>,
+        title: &str,
+    ) -> Result<(), Box<dyn std::error::Error>>
+    where
+        DB::ErrorType: 'static,
+    {
+        root_area.fill(&WHITE)?;
+        let root_area = root_area.titled(title, ("sans-serif", 50))?;
+        let areas = root_area.split_evenly((3, 1));
+
+        self.draw_line(&areas[0], "loss", |e| e.loss, RED)?;
+        self.draw_line(&areas[1], "accuracy(%)", |e| e.accuracy, GREEN)?;
+        self.draw_line(&areas[2], "learning rate", |e| e.learning_rate, BLUE)?;
+
+        root_area.present()?;
+        Ok(())
+    }
+
+    fn draw_line<DB: DrawingBackend>(
+        &self,
+        area: &DrawingArea<DB, Shift>,
+        name: &str,
+        value: fn(&HistoryEntry) -> f64,
+        color: RGBColor,
+    ) -> Result<(), Box<dyn std::error::Error>>
+    where
+        DB::ErrorType: 'static,
+    {
+        let last_step = self.entries.last().map_or(1, |e| e.step.max(1));
+        let (mut y_min, mut y_max) = self
+            .entries
+            .iter()
+            .map(value)
+            .fold((f64::MAX, f64::MIN), |(lo, hi), v| (lo.min(v), hi.max(v)));
+        if self.entries.is_empty() {
+            (y_min, y_max) = (0.0, 1.0);
+        }
+        // flat lines still need a non empty range to be drawn.
+        let padding = ((y_max - y_min) * 0.05).max(1e-3);
+
+        let mut cc = ChartBuilder::on(area)
+            .margin(10)
+            .caption(name, ("sans-serif", 30))
+            .set_label_area_size(LabelAreaPosition::Left, 60)
+            .set_label_area_size(LabelAreaPosition::Bottom, 40)
+            .build_cartesian_2d(0..last_step, (y_min - padding)..(y_max + padding))?;
+        cc.configure_mesh()
+            .x_desc("step")
+            .y_label_formatter(&|v| format!("{:.3}", v))
+            .draw()?;
+
+        cc.draw_series(LineSeries::new(
+            self.entries.iter().map(|e| (e.step, value(e))),
+            color.stroke_width(2),
+        ))?;
+        Ok(())
+    }
+}
@@ -1,4 +1,10 @@
 mod binary_class;
+pub mod checkpoint_class;
+mod history;
+pub mod multi_class;
+pub mod parallel_class;
+pub mod profile;
+pub mod tape_bench;
 mod utils;
 pub use binary_class::_binary_classifier;
 
@@ -23,7 +29,7 @@ pub fn _micrograd_simple1() {
     let w2 = MVal::new_lab(1, "w2");
 
     // bias of the neuron.
-    let b = 6.8813735870195432;
+    let b = 6.881_373_587_019_543;
 
     let x1w1 = x1 * w1;
     let x2w2 = x2 * w2;
@@ -31,7 +37,9 @@ pub fn _micrograd_simple1() {
     let n = x1w1x2w2 + b;
     let mut o = n.tanh();
     // o.backward_debug();
-    o.backward();
+    if let Err(err) = o.backward() {
+        dbg!(err);
+    }
 }
 
 pub fn _micrograd_simple2() {
@@ -44,7 +52,7 @@ pub fn _micrograd_simple2() {
     let w2 = MVal::new_lab(1, "w2");
 
     // bias of the neuron.
-    let b = 6.8813735870195432;
+    let b = 6.881_373_587_019_543;
 
     let x1w1 = x1 * w1;
     let x2w2 = x2 * w2;
@@ -57,7 +65,9 @@ pub fn _micrograd_simple2() {
     let mut o = (exp.clone() - 1) / (exp + 1);
 
     // o.backward_debug();
-    o.backward();
+    if let Err(err) = o.backward() {
+        dbg!(err);
+    }
 }
 
 pub fn _micrograd_clone1() {
@@ -65,7 +75,9 @@ pub fn _micrograd_clone1() {
     let a = MVal::new_lab(2, "a");
     let mut b = a.clone() + a;
     // b.backward_debug();
-    b.backward();
+    if let Err(err) = b.backward() {
+        dbg!(err);
+    }
 }
 
 pub fn _micrograd_clone2() {
@@ -77,7 +89,9 @@ pub fn _micrograd_clone2() {
     let e = a + b;
     let mut f = d * e;
     // f.backward_debug();
-    f.backward();
+    if let Err(err) = f.backward() {
+        dbg!(err);
+    }
 }
 
 pub fn _mlp_example() {
@@ -89,13 +103,13 @@ pub fn _mlp_example() {
         [0.5, 1.0, 1.0],
         [1.0, 1.0, -1.0],
     ];
-    let ys = vec![1.0, -1.0, -1.0, 1.0];
+    let ys = [1.0, -1.0, -1.0, 1.0];
 
     println!("\nprediction before training:");
     for x in xs.clone() {
         let ypre = mlp
             .forward(x.to_vec())
-            .get(0)
+            .first()
             .unwrap_or(&MVal::new(0))
             .clone();
         println!("ypred:{}", ypre)
@@ -107,7 +121,7 @@ pub fn _mlp_example() {
             // last layer has just one neuron.
             let ypre = mlp
                 .forward(x.to_vec())
-                .get(0)
+                .first()
                 .unwrap_or(&MVal::new(0))
                 .clone();
             ypred.push(ypre);
@@ -116,12 +130,15 @@ pub fn _mlp_example() {
         // loss computed using mean squared value
         let mut loss = MVal::new(0);
         for (i, ypre) in ypred.into_iter().enumerate() {
-            let yact = ys.get(i).unwrap_or(&0.0).clone();
+            let yact = *ys.get(i).unwrap_or(&0.0);
             loss = loss + (ypre.clone() - yact).pow(2);
         }
 
         loss.zero_grad();
-        loss.backward();
+        if let Err(err) = loss.backward() {
+            dbg!(err);
+            return;
+        }
 
         for p in mlp.parameters() {
             let grad = p.grad();
@@ -137,7 +154,7 @@ pub fn _mlp_example() {
         // last layer has just one neuron.
         let ypre = mlp
             .forward(x.to_vec())
-            .get(0)
+            .first()
             .unwrap_or(&MVal::new(0))
             .clone();
         println!("ypred:{}", ypre)
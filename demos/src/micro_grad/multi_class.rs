@@ -0,0 +1,90 @@
+use crate::micro_grad::utils::{_scatter_plot_custom, make_spiral, ColorMode, PlotOptions};
+use micrograd::{argmax, cross_entropy, metrics, ActivationType, Layer, MVal, MLP};
+use plotters::prelude::LogScalable;
+
+/// trains a MLP to classify the points of each arm of a spiral using softmax cross entropy loss.
+pub fn _multi_class_classifier() {
+    let n_classes = 3;
+    let (inps, classes) = make_spiral(50, n_classes, 0.2);
+    let plot_options = PlotOptions {
+        color_mode: ColorMode::Categorical,
+        ..Default::default()
+    };
+    let category: Vec<f64> = classes.iter().map(|&c| c.as_f64()).collect();
+    if let Err(err) = _scatter_plot_custom(
+        &inps,
+        &category,
+        "Spiral Training Sample",
+        "./images/spiral_training_sample.png",
+        &plot_options,
+    ) {
+        dbg!(err);
+        return;
+    }
+
+    // the last layer has one linear output(logit) for each class.
+    let mut model = MLP::from_layers(vec![
+        Layer::new(2, 16),
+        Layer::new(16, 16),
+        Layer::new_custom(16, n_classes, ActivationType::Linear),
+    ]);
+
+    let batch: Vec<Vec<f64>> = inps.rows().into_iter().map(|row| row.to_vec()).collect();
+    let steps = 300;
+    println!("\ntraining the model:");
+    for k in 0..steps {
+        let mut loss = MVal::new(0.0);
+        let mut preds = vec![];
+        for (logits, &class) in model.forward_batch(&batch).iter().zip(&classes) {
+            loss = loss + cross_entropy(logits, class);
+            preds.push(argmax(&metrics::values(logits)));
+        }
+        let mut avg_loss = loss / batch.len().as_f64();
+        let accuracy = metrics::accuracy(&preds, &classes) * 100.0;
+        if k % 10 == 0 {
+            println!("step:{k}, loss:{}, accuracy:{accuracy}%", avg_loss.get());
+        }
+
+        avg_loss.zero_grad();
+        if let Err(err) = avg_loss.backward() {
+            dbg!(err);
+            return;
+        }
+
+        let learning_rate = 0.5 - (0.45 * k.as_f64() / steps.as_f64());
+        for p in model.parameters() {
+            p.set(p.get() - MVal::new(learning_rate * p.grad()));
+        }
+    }
+
+    // make prediction for newly generated data
+    let (inps, classes) = make_spiral(50, n_classes, 0.2);
+    let preds: Vec<usize> = inps
+        .rows()
+        .into_iter()
+        .map(|row| model.predict_class(&row.to_vec()))
+        .collect();
+
+    println!("\naccuracy:{}", metrics::accuracy(&preds, &classes));
+    for (class, accuracy) in metrics::per_class_accuracy(&preds, &classes, n_classes)
+        .iter()
+        .enumerate()
+    {
+        println!("class:{class}, accuracy:{accuracy}");
+    }
+    println!(
+        "confusion matrix:{:?}",
+        metrics::confusion_matrix(&preds, &classes, n_classes)
+    );
+
+    let category: Vec<f64> = preds.iter().map(|&c| c.as_f64()).collect();
+    if let Err(err) = _scatter_plot_custom(
+        &inps,
+        &category,
+        "Spiral Prediction Sample",
+        "./images/spiral_prediction.png",
+        &plot_options,
+    ) {
+        dbg!(err);
+    }
+}
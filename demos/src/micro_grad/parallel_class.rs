@@ -0,0 +1,59 @@
+use crate::micro_grad::utils::make_moons;
+use micrograd::{metrics, parallel_backward, AVal, AMLP};
+use plotters::prelude::LogScalable;
+
+/// same as the binary classifier but the gradients of each shard of the training samples
+/// are computed on its own worker thread.
+pub fn _parallel_binary_classifier() {
+    let (inps, outs) = make_moons(200, 0.1);
+    let samples: Vec<(Vec<f64>, f64)> = inps
+        .rows()
+        .into_iter()
+        .map(|row| row.to_vec())
+        .zip(outs.iter().copied())
+        .collect();
+    let n_shards = 4;
+    let shards: Vec<&[(Vec<f64>, f64)]> =
+        samples.chunks(samples.len().div_ceil(n_shards)).collect();
+
+    let model = AMLP::new(2, vec![16, 16, 1]);
+    let params = model.parameters();
+
+    // svm "max-margin" loss of a shard. loss will increase when ouput and prediction is not matching
+    let shard_loss = |shard: &&[(Vec<f64>, f64)]| {
+        let mut loss = AVal::new(0.0);
+        for (inp, out) in shard.iter() {
+            let pre = model
+                .forward(inp.clone())
+                .first()
+                .expect("expecting single output since the last layer has single neuron")
+                .clone();
+            loss = loss + (1.0 - pre * *out);
+        }
+        loss
+    };
+
+    println!("\ntraining the model on {n_shards} threads:");
+    for k in 0..100 {
+        for p in &params {
+            p.set_grad(AVal::new(0.0));
+        }
+        let loss = parallel_backward(&params, &shards, shard_loss);
+        let n = samples.len().as_f64();
+
+        // accuracy will be high when both output and prediction has the same sign.
+        let scores: Vec<f64> = samples
+            .iter()
+            .map(|(inp, _)| model.forward(inp.clone())[0].get())
+            .collect();
+        let accuracy = metrics::accuracy(&metrics::binary_labels(&scores, 0.0), &outs) * 100.0;
+        println!("step:{k}, loss:{}, accuracy:{accuracy}%", loss / n);
+
+        // optimisation
+        let learning_rate = 1.0 - (0.9 * k.as_f64() / 100.0);
+        for p in &params {
+            // the summed loss is averaged, so the gradients are averaged as well.
+            p.set(AVal::new(p.get() - learning_rate * p.grad() / n));
+        }
+    }
+}
@@ -0,0 +1,34 @@
+use crate::micro_grad::utils::make_moons;
+use micrograd::{profile::profile, MVal, MLP};
+use plotters::prelude::LogScalable;
+
+/// prints the size of the graph and the forward/backward time of the loss
+/// for MLPs and batches of different sizes.
+pub fn _profile() {
+    let (inps, outs) = make_moons(200, 0.1);
+    let samples: Vec<Vec<f64>> = inps.rows().into_iter().map(|row| row.to_vec()).collect();
+
+    for (layers, batch_size) in [
+        (vec![16, 16, 1], 50),
+        (vec![16, 16, 1], 200),
+        (vec![32, 32, 1], 200),
+    ] {
+        let model = MLP::new(2, layers.clone());
+        let batch = &samples[..batch_size];
+        let profile = match profile(|| {
+            let mut loss = MVal::new(0.0);
+            for (pre, out) in model.forward_batch(batch).into_iter().zip(&outs) {
+                loss = loss + (1.0 - pre[0].clone() * *out).relu();
+            }
+            loss / batch_size.as_f64()
+        }) {
+            Ok(profile) => profile,
+            Err(err) => {
+                dbg!(err);
+                return;
+            }
+        };
+        println!("\nlayers:{layers:?}, batch size:{batch_size}");
+        println!("{profile}");
+    }
+}
@@ -0,0 +1,63 @@
+use crate::micro_grad::utils::make_moons;
+use micrograd::{MVal, Tape, TapeMLP, MLP};
+use plotters::prelude::LogScalable;
+use std::time::{Duration, Instant};
+
+/// compares the time taken by a training step(forward, loss, backward and update)
+/// of the MVal based MLP and the Tape based TapeMLP on the moons data set.
+pub fn _tape_benchmark() {
+    let (inps, outs) = make_moons(200, 0.1);
+    let batch: Vec<Vec<f64>> = inps.rows().into_iter().map(|row| row.to_vec()).collect();
+    let steps = 10;
+    let learning_rate = 0.5;
+
+    // MVal based MLP
+    let mut model = MLP::new(2, vec![16, 16, 1]);
+    let start = Instant::now();
+    for _ in 0..steps {
+        let mut loss = MVal::new(0.0);
+        for (pre, out) in model.forward_batch(&batch).into_iter().zip(&outs) {
+            // svm "max-margin" loss. loss will increase when ouput and prediction is not matching
+            loss = loss + (1.0 - pre[0].clone() * *out);
+        }
+        let mut avg_loss = loss / batch.len().as_f64();
+        avg_loss.zero_grad();
+        if let Err(err) = avg_loss.backward() {
+            dbg!(err);
+            return;
+        }
+        for p in model.parameters() {
+            p.set(MVal::new(p.get() - learning_rate * p.grad()));
+        }
+    }
+    let mval_time = start.elapsed();
+
+    // Tape based MLP
+    let tape = Tape::new();
+    let tape_model = TapeMLP::new(&tape, 2, vec![16, 16, 1]);
+    let params_len = tape.len();
+    let start = Instant::now();
+    for _ in 0..steps {
+        let mut loss = tape.var(0.0);
+        for (inp, out) in batch.iter().zip(&outs) {
+            let pre = tape_model.forward(&tape, inp)[0];
+            loss = loss + (1.0 - pre * *out);
+        }
+        let avg_loss = loss / batch.len().as_f64();
+        tape.backward(avg_loss);
+        for p in tape_model.parameters(&tape) {
+            p.set(p.get() - learning_rate * p.grad());
+        }
+        // drop the graph of this step but keep the parameters.
+        tape.truncate(params_len);
+    }
+    let tape_time = start.elapsed();
+
+    let per_step = |d: Duration| d.as_secs_f64() * 1000.0 / steps.as_f64();
+    println!(
+        "MVal: {:.2}ms/step, Tape: {:.2}ms/step, speedup: {:.1}x",
+        per_step(mval_time),
+        per_step(tape_time),
+        mval_time.as_secs_f64() / tape_time.as_secs_f64()
+    );
+}
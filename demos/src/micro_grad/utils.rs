@@ -1,15 +1,106 @@
-use ndarray::Array2;
-use plotters::prelude::*;
+use micrograd::MLP;
+use ndarray::{s, Array2};
+use plotters::{
+    coord::{types::RangedCoordf32, Shift},
+    prelude::*,
+};
 use rand::{
     distributions::{Distribution, Uniform},
+    seq::SliceRandom,
     Rng,
 };
-use std::f64::consts::PI;
+use rand_distr::Normal;
+use std::{f64::consts::PI, path::Path};
+
+#[allow(dead_code)]
+#[derive(Debug, Clone)]
+pub enum MoonsType {
+    /// Same as scikit-learn: evenly spaced points on each moon with
+    /// isotropic Gaussian noise added to both coordinates.
+    SkLearn,
+    /// Random angles for both moons and uniform noise applied only to the radius.
+    RandomAngle,
+}
 
 /// returns `([[x1,y1], [x2,y2], [x3,y3],...], [1, -1, 1,...])`
-/// 1 means (x,y) belongs to upper halft moon else lower half moon
+/// 1 means (x,y) belongs to upper halft moon else lower half moon.
+/// Here default moons type will be SkLearn and the samples are shuffled.
 pub fn make_moons(n_samples: usize, noise: f64) -> (Array2<f64>, Vec<f64>) {
+    make_moons_custom(n_samples, noise, true, MoonsType::SkLearn)
+}
+
+/// Same as make_moons but with custom moons type and shuffle flag.
+/// shuffle - if false, all the upper moon points come first followed by the lower moon points.
+pub fn make_moons_custom(
+    n_samples: usize,
+    noise: f64,
+    shuffle: bool,
+    moons_type: MoonsType,
+) -> (Array2<f64>, Vec<f64>) {
     let mut rng = rand::thread_rng();
+    let (mut data, mut labels) = match moons_type {
+        MoonsType::SkLearn => sklearn_moons(n_samples, noise, &mut rng),
+        MoonsType::RandomAngle => random_angle_moons(n_samples, noise, &mut rng),
+    };
+
+    if shuffle {
+        let mut order: Vec<usize> = (0..n_samples).collect();
+        order.shuffle(&mut rng);
+        data = order.iter().map(|&i| data[i]).collect();
+        labels = order.iter().map(|&i| labels[i]).collect();
+    }
+
+    (
+        Array2::from_shape_vec((n_samples, 2), data.concat())
+            .expect("error constructing array from vector"),
+        labels,
+    )
+}
+
+/// upper moon gets n_samples/2 points and lower moon gets the remaining points,
+/// so odd sample counts will put the extra point in the lower moon like scikit-learn.
+fn sklearn_moons(n_samples: usize, noise: f64, rng: &mut impl Rng) -> (Vec<[f64; 2]>, Vec<f64>) {
+    let n_upper = n_samples / 2;
+    let n_lower = n_samples - n_upper;
+    let normal = Normal::new(0.0, noise).expect("noise should be a finite non negative number");
+
+    let mut data = Vec::with_capacity(n_samples);
+    let mut labels: Vec<f64> = Vec::with_capacity(n_samples);
+
+    // evenly spaced angles between 0 and PI(both inclusive) like numpy linspace.
+    let linspace = |n: usize, i: usize| {
+        if n > 1 {
+            PI * i as f64 / (n - 1) as f64
+        } else {
+            0.0
+        }
+    };
+
+    for i in 0..n_upper {
+        let angle = linspace(n_upper, i);
+        data.push([angle.cos(), angle.sin()]);
+        labels.push(1.0);
+    }
+    for i in 0..n_lower {
+        // Shift and mirror for the second moon(half cirle points)
+        let angle = linspace(n_lower, i);
+        data.push([1.0 - angle.cos(), 0.5 - angle.sin()]);
+        labels.push(-1.0);
+    }
+
+    for point in data.iter_mut() {
+        point[0] += normal.sample(rng);
+        point[1] += normal.sample(rng);
+    }
+
+    (data, labels)
+}
+
+fn random_angle_moons(
+    n_samples: usize,
+    noise: f64,
+    rng: &mut impl Rng,
+) -> (Vec<[f64; 2]>, Vec<f64>) {
     let uniform = Uniform::new(0.0, PI);
 
     // Generate points for the first moon
@@ -17,7 +108,7 @@ pub fn make_moons(n_samples: usize, noise: f64) -> (Array2<f64>, Vec<f64>) {
     let mut labels: Vec<f64> = Vec::with_capacity(n_samples);
 
     for i in 0..n_samples {
-        let angle = uniform.sample(&mut rng);
+        let angle = uniform.sample(rng);
         // the following radius is used to add some randomness to the generated values
         let radius = 1.0 + noise * rng.gen::<f64>();
 
@@ -35,29 +126,278 @@ pub fn make_moons(n_samples: usize, noise: f64) -> (Array2<f64>, Vec<f64>) {
         }
     }
 
+    (data, labels)
+}
+
+/// data and labels of the samples.
+pub type Samples = (Array2<f64>, Vec<f64>);
+
+/// splits the samples into (training, validation) sets. The last `val_fraction` of the samples
+/// are used for validation, so the samples should be shuffled before the split.
+pub fn train_val_split(
+    data: &Array2<f64>,
+    labels: &[f64],
+    val_fraction: f64,
+) -> (Samples, Samples) {
+    let n_val = (data.nrows() as f64 * val_fraction.clamp(0.0, 1.0)).round() as usize;
+    let n_train = data.nrows() - n_val;
+    (
+        (
+            data.slice(s![..n_train, ..]).to_owned(),
+            labels[..n_train].to_vec(),
+        ),
+        (
+            data.slice(s![n_train.., ..]).to_owned(),
+            labels[n_train..].to_vec(),
+        ),
+    )
+}
+
+/// returns `([[x1,y1], [x2,y2],...], [0, 2, 1,...])` with n_per_class points for each class.
+/// Points of each class lie on its own arm of a spiral starting from the center.
+/// The samples are shuffled and noise is the standard deviation of the Gaussian noise added to the angle.
+#[allow(dead_code)]
+pub fn make_spiral(n_per_class: usize, n_classes: usize, noise: f64) -> (Array2<f64>, Vec<usize>) {
+    let mut rng = rand::thread_rng();
+    let normal = Normal::new(0.0, noise).expect("noise should be a finite non negative number");
+
+    let mut samples = Vec::with_capacity(n_per_class * n_classes);
+    for class in 0..n_classes {
+        for i in 0..n_per_class {
+            // radius goes from 0 to 1 and each arm covers 4 radians of the angle.
+            let t = if n_per_class > 1 {
+                i as f64 / (n_per_class - 1) as f64
+            } else {
+                0.0
+            };
+            let angle = (class as f64 + t) * 4.0 + normal.sample(&mut rng);
+            samples.push(([t * angle.sin(), t * angle.cos()], class));
+        }
+    }
+    samples.shuffle(&mut rng);
+
+    let (data, labels): (Vec<[f64; 2]>, Vec<usize>) = samples.into_iter().unzip();
     (
-        Array2::from_shape_vec((n_samples, 2), data.concat())
+        Array2::from_shape_vec((data.len(), 2), data.concat())
             .expect("error constructing array from vector"),
         labels,
     )
 }
 
+/// how the category value of each data point is mapped to a colour.
+#[allow(dead_code)]
+#[derive(Debug, Clone)]
+pub enum ColorMode {
+    /// blue below -0.9, green above 0.9 and red for everything in between.
+    Threshold,
+    /// one palette colour for each distinct category value. Used for class labels.
+    Categorical,
+    /// category is treated as a continuous value(e.g. prediction score)
+    /// and mapped on the viridis colour scale between its min and max.
+    Continuous,
+}
+
+#[derive(Debug, Clone)]
+pub struct PlotOptions {
+    /// (start, end) of the x axis. It will be computed from the data when None.
+    pub x_range: Option<(f64, f64)>,
+    /// (start, end) of the y axis. It will be computed from the data when None.
+    pub y_range: Option<(f64, f64)>,
+    /// added on both sides of the ranges computed from the data.
+    pub padding: f64,
+    /// (width, height) of the image in pixels.
+    pub size: (u32, u32),
+    pub color_mode: ColorMode,
+}
+
+impl Default for PlotOptions {
+    fn default() -> Self {
+        Self {
+            x_range: None,
+            y_range: None,
+            padding: 0.5,
+            size: (1024, 768),
+            color_mode: ColorMode::Threshold,
+        }
+    }
+}
+
+impl PlotOptions {
+    /// returns the x and y axis ranges, computing the missing ones from the data extent.
+    fn ranges(&self, data: &Array2<f64>) -> ((f64, f64), (f64, f64)) {
+        let extent = |col: usize| {
+            let (lo, hi) = data
+                .column(col)
+                .iter()
+                .fold((f64::MAX, f64::MIN), |(lo, hi), &v| (lo.min(v), hi.max(v)));
+            if lo > hi {
+                // empty data
+                (-1.0, 1.0)
+            } else {
+                (lo - self.padding, hi + self.padding)
+            }
+        };
+        (
+            self.x_range.unwrap_or_else(|| extent(0)),
+            self.y_range.unwrap_or_else(|| extent(1)),
+        )
+    }
+}
+
+/// SVG is used when the file_path ends with `.svg` otherwise PNG bitmap is used.
+pub fn is_svg(file_path: &str) -> bool {
+    Path::new(file_path)
+        .extension()
+        .is_some_and(|ext| ext.eq_ignore_ascii_case("svg"))
+}
+
+/// Here default plot options will be used.
 pub fn _scatter_plot(
     data: &Array2<f64>,
-    category: &Vec<f64>,
+    category: &[f64],
+    title: &str,
+    file_path: &str,
+) -> Result<(), Box<dyn std::error::Error>> {
+    _scatter_plot_custom(data, category, title, file_path, &PlotOptions::default())
+}
+
+/// Same as _scatter_plot but with custom axis ranges, image size, colour mode.
+pub fn _scatter_plot_custom(
+    data: &Array2<f64>,
+    category: &[f64],
     title: &str,
     file_path: &str,
+    options: &PlotOptions,
 ) -> Result<(), Box<dyn std::error::Error>> {
-    // "./scatter_plot.png"
-    let root_area = BitMapBackend::new(file_path, (1024, 768)).into_drawing_area();
+    if is_svg(file_path) {
+        let root_area = SVGBackend::new(file_path, options.size).into_drawing_area();
+        draw_scatter(root_area, data, category, title, options)
+    } else {
+        let root_area = BitMapBackend::new(file_path, options.size).into_drawing_area();
+        draw_scatter(root_area, data, category, title, options)
+    }
+}
+
+fn draw_scatter<DB: DrawingBackend>(
+    root_area: DrawingArea<DB, Shift>,
+    data: &Array2<f64>,
+    category: &[f64],
+    title: &str,
+    options: &PlotOptions,
+) -> Result<(), Box<dyn std::error::Error>>
+where
+    DB::ErrorType: 'static,
+{
     root_area.fill(&WHITE)?;
+    let root_area = root_area.titled(title, ("sans-serif", 60))?;
+
+    let (x_range, y_range) = options.ranges(data);
+    let mut cc = build_chart(&root_area, x_range, y_range)?;
+
+    // Draw the scatter points
+    draw_points(&mut cc, data, category, &options.color_mode)?;
+
+    // Add labels to points (optional)
+    // for r in data.rows() {
+    //     let x = r.get(0).unwrap().clone() as f32;
+    //     let y = r.get(1).unwrap().clone() as f32;
+    //     let label = format!("({:.1}, {:.1})", x, y);
+    //     cc.draw_series([Text::new(label, (x, y), ("sans-serif", 12).into_font())])?;
+    // }
 
+    root_area.present()?;
+    Ok(())
+}
+
+/// evaluates the model on a dense grid over the data extent and draws the decision function
+/// as a heatmap under the scatter points. `resolution` is the number of grid cells along each axis.
+/// The model is expected to have single output in the last layer.
+pub fn _decision_boundary_plot(
+    model: &MLP,
+    data: &Array2<f64>,
+    category: &[f64],
+    resolution: usize,
+    title: &str,
+    file_path: &str,
+    options: &PlotOptions,
+) -> Result<(), Box<dyn std::error::Error>> {
+    if is_svg(file_path) {
+        let root_area = SVGBackend::new(file_path, options.size).into_drawing_area();
+        draw_decision_boundary(root_area, model, data, category, resolution, title, options)
+    } else {
+        let root_area = BitMapBackend::new(file_path, options.size).into_drawing_area();
+        draw_decision_boundary(root_area, model, data, category, resolution, title, options)
+    }
+}
+
+fn draw_decision_boundary<DB: DrawingBackend>(
+    root_area: DrawingArea<DB, Shift>,
+    model: &MLP,
+    data: &Array2<f64>,
+    category: &[f64],
+    resolution: usize,
+    title: &str,
+    options: &PlotOptions,
+) -> Result<(), Box<dyn std::error::Error>>
+where
+    DB::ErrorType: 'static,
+{
+    root_area.fill(&WHITE)?;
     let root_area = root_area.titled(title, ("sans-serif", 60))?;
 
-    let mut cc = ChartBuilder::on(&root_area)
+    let ((x_min, x_max), (y_min, y_max)) = options.ranges(data);
+    let x_step = (x_max - x_min) / resolution as f64;
+    let y_step = (y_max - y_min) / resolution as f64;
+    let mut cc = build_chart(&root_area, (x_min, x_max), (y_min, y_max))?;
+
+    // score of each grid cell is evaluated at the center of the cell.
+    let mut cells = Vec::with_capacity(resolution * resolution);
+    for i in 0..resolution {
+        for j in 0..resolution {
+            let x = x_min + x_step * i as f64;
+            let y = y_min + y_step * j as f64;
+            let score = *model
+                .predict(&[x + x_step / 2.0, y + y_step / 2.0])
+                .first()
+                .expect("expecting single output since the last layer has single neuron");
+            cells.push((x, y, score));
+        }
+    }
+
+    cc.draw_series(cells.into_iter().map(|(x, y, score)| {
+        Rectangle::new(
+            [
+                (x as f32, y as f32),
+                ((x + x_step) as f32, (y + y_step) as f32),
+            ],
+            boundary_color(score).filled(),
+        )
+    }))?;
+
+    draw_points(&mut cc, data, category, &options.color_mode)?;
+
+    root_area.present()?;
+    Ok(())
+}
+
+fn build_chart<'a, DB: DrawingBackend>(
+    root_area: &'a DrawingArea<DB, Shift>,
+    x_range: (f64, f64),
+    y_range: (f64, f64),
+) -> Result<
+    ChartContext<'a, DB, Cartesian2d<RangedCoordf32, RangedCoordf32>>,
+    Box<dyn std::error::Error>,
+>
+where
+    DB::ErrorType: 'static,
+{
+    let mut cc = ChartBuilder::on(root_area)
         .margin(5)
         .set_all_label_area_size(50)
-        .build_cartesian_2d(-2.5f32..3.0, -1.2f32..1.2f32)?;
+        .build_cartesian_2d(
+            x_range.0 as f32..x_range.1 as f32,
+            y_range.0 as f32..y_range.1 as f32,
+        )?;
     cc.configure_mesh()
         .x_labels(20)
         .y_labels(10)
@@ -65,47 +405,75 @@ pub fn _scatter_plot(
         .x_label_formatter(&|v| format!("{:.1}", v))
         .y_label_formatter(&|v| format!("{:.1}", v))
         .draw()?;
+    Ok(cc)
+}
+
+/// light blue for negative scores and light green for positive scores.
+/// the colour fades to white as the score gets closer to the decision boundary(0).
+fn boundary_color(score: f64) -> RGBColor {
+    let strength = score.clamp(-1.0, 1.0).abs();
+    let fade = |c: u8| (255.0 - (255.0 - c as f64) * 0.4 * strength) as u8;
+    let base = if score < 0.0 { BLUE } else { GREEN };
+    RGBColor(fade(base.0), fade(base.1), fade(base.2))
+}
+
+/// draws each data point coloured by its category based on the colour mode.
+fn draw_points<DB: DrawingBackend>(
+    cc: &mut ChartContext<DB, Cartesian2d<RangedCoordf32, RangedCoordf32>>,
+    data: &Array2<f64>,
+    category: &[f64],
+    color_mode: &ColorMode,
+) -> Result<(), Box<dyn std::error::Error>>
+where
+    DB::ErrorType: 'static,
+{
+    // sorted distinct category values. index of a value in this list is its palette index.
+    let mut classes = category.to_vec();
+    classes.sort_by(f64::total_cmp);
+    classes.dedup();
+    let (min, max) = match (classes.first(), classes.last()) {
+        (Some(&min), Some(&max)) if min < max => (min, max),
+        (Some(&min), _) => (min - 1.0, min + 1.0),
+        _ => (0.0, 1.0),
+    };
 
-    // Draw the scatter points
     let mut i = 0;
     cc.draw_series(data.rows().into_iter().map(|row| {
-        let x = row
+        let x = *row
             .get(0)
-            .expect("expecting the x axis value in the array 2nd dimention")
-            .clone() as f32;
-        let y = row
+            .expect("expecting the x axis value in the array 2nd dimention") as f32;
+        let y = *row
             .get(1)
-            .expect("expecting the x axis value in the array 2nd dimention")
-            .clone() as f32;
-        let cat = category
+            .expect("expecting the x axis value in the array 2nd dimention") as f32;
+        let cat = *category
             .get(i)
-            .expect("expectinve category number each data point")
-            .clone();
+            .expect("expectinve category number each data point");
         i += 1;
-        Circle::new(
-            (x, y),
-            5,
-            ShapeStyle {
-                color: if cat < -0.9 {
+        let color = match color_mode {
+            ColorMode::Threshold => {
+                if cat < -0.9 {
                     BLUE.to_rgba()
                 } else if cat > 0.9 {
                     GREEN.to_rgba()
                 } else {
                     RED.to_rgba()
-                },
+                }
+            }
+            ColorMode::Categorical => {
+                let idx = classes.partition_point(|&c| c < cat);
+                Palette99::pick(idx).to_rgba()
+            }
+            ColorMode::Continuous => ViridisRGB::get_color_normalized(cat, min, max).to_rgba(),
+        };
+        Circle::new(
+            (x, y),
+            5,
+            ShapeStyle {
+                color,
                 filled: true,
                 stroke_width: 1,
             },
         )
     }))?;
-
-    // Add labels to points (optional)
-    // for r in data.rows() {
-    //     let x = r.get(0).unwrap().clone() as f32;
-    //     let y = r.get(1).unwrap().clone() as f32;
-    //     let label = format!("({:.1}, {:.1})", x, y);
-    //     cc.draw_series([Text::new(label, (x, y), ("sans-serif", 12).into_font())])?;
-    // }
-
     Ok(())
 }
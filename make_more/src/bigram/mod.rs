@@ -0,0 +1,60 @@
+use micrograd::MVal;
+
+use crate::{model::LanguageModel, vocab::Vocab};
+
+/*
+What is Bigram?
+    - The simplest character level language model. It only looks at the previous character and
+      predicts the next character by counting how often each pair of characters appears in
+      the training words.
+            `P(next | prev) = count(prev, next) / count(prev, *)`
+
+    - smoothing is added to every count, so that the pairs never seen in the training words
+      don't get the probability 0(and an infinite loss).
+
+    - The logits are the log of the counts, so softmax of the logits gives back the probabilities.
+*/
+
+#[derive(Debug, Clone)]
+pub struct Bigram {
+    vocab: Vocab,
+    // counts[prev][next] including the smoothing.
+    counts: Vec<Vec<f64>>,
+}
+
+impl Bigram {
+    /// counts the pairs of characters of the words. The words are wrapped with the END token.
+    /// Panics if a word has a character that is not in the Vocab.
+    pub fn new(vocab: Vocab, words: &[String], smoothing: f64) -> Self {
+        let mut counts = vec![vec![smoothing; vocab.len()]; vocab.len()];
+        let (contexts, targets) = crate::vocab::build_dataset(words, &vocab, 1);
+        for (context, &next) in contexts.iter().zip(&targets) {
+            counts[context[0]][next] += 1.0;
+        }
+        Self { vocab, counts }
+    }
+
+    /// probability of the next token following the prev token.
+    pub fn prob(&self, prev: usize, next: usize) -> f64 {
+        let row = &self.counts[prev];
+        row[next] / row.iter().sum::<f64>()
+    }
+}
+
+impl LanguageModel for Bigram {
+    fn vocab(&self) -> &Vocab {
+        &self.vocab
+    }
+
+    fn block_size(&self) -> usize {
+        1
+    }
+
+    fn logits(&self, context: &[usize]) -> Vec<MVal> {
+        let prev = context.last().copied().unwrap_or(0);
+        self.counts[prev]
+            .iter()
+            .map(|c| MVal::new(c.ln()))
+            .collect()
+    }
+}
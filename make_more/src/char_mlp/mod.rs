@@ -0,0 +1,93 @@
+use micrograd::{cross_entropy, ActivationType, Embedding, Layer, MVal, Result};
+
+use crate::{model::LanguageModel, vocab::Vocab};
+
+/*
+What is CharMLP?
+    - The neural language model of Bengio et al. 2003 at the character level. Each token of the
+      context is mapped to a learnable vector by the Embedding, the vectors are concatenated and
+      passed to a Tanh hidden Layer followed by a Linear output Layer with one logit per token.
+
+    - Unlike the Bigram it can look at more than one previous character and characters with
+      similar usage get similar vectors, so it generalises to the contexts never seen in training.
+
+How to train it?
+    - Build the (context, target) samples with `build_dataset` using the same block_size
+      as the model and call train_step with random mini batches of them.
+            `let loss = model.train_step(&contexts, &targets, 0.1)?;`
+*/
+
+#[derive(Debug)]
+pub struct CharMLP {
+    vocab: Vocab,
+    block_size: usize,
+    embedding: Embedding,
+    hidden: Layer,
+    output: Layer,
+}
+
+impl CharMLP {
+    /// emb_dim - length of the vector of each token.
+    /// n_hidden - number of Neurons in the hidden layer.
+    pub fn new(vocab: Vocab, block_size: usize, emb_dim: usize, n_hidden: usize) -> Self {
+        let n_tokens = vocab.len();
+        Self {
+            vocab,
+            block_size,
+            embedding: Embedding::new(n_tokens, emb_dim),
+            hidden: Layer::new(block_size * emb_dim, n_hidden),
+            output: Layer::new_custom(n_hidden, n_tokens, ActivationType::Linear),
+        }
+    }
+
+    /// average cross entropy of the targets given the contexts.
+    pub fn loss(&self, contexts: &[Vec<usize>], targets: &[usize]) -> MVal {
+        let mut loss = MVal::new(0.0);
+        for (context, &target) in contexts.iter().zip(targets) {
+            loss = loss + cross_entropy(&self.logits(context), target);
+        }
+        loss / contexts.len().max(1) as f64
+    }
+
+    /// one step of gradient descent on the batch. returns the loss of the batch before the update
+    /// or the error of backward.
+    pub fn train_step(
+        &mut self,
+        contexts: &[Vec<usize>],
+        targets: &[usize],
+        learning_rate: f64,
+    ) -> Result<f64> {
+        let mut loss = self.loss(contexts, targets);
+        // embedding rows not used in this batch are not part of the graph of the loss.
+        self.embedding.zero_grad();
+        loss.zero_grad();
+        loss.backward()?;
+        for p in self.parameters() {
+            p.set(MVal::new(p.get() - learning_rate * p.grad()));
+        }
+        Ok(loss.get())
+    }
+
+    /// returns the parameters of the embedding, hidden and output layers.
+    pub fn parameters(&self) -> Vec<MVal> {
+        let mut params = self.embedding.parameters();
+        params.append(&mut self.hidden.parameters());
+        params.append(&mut self.output.parameters());
+        params
+    }
+}
+
+impl LanguageModel for CharMLP {
+    fn vocab(&self) -> &Vocab {
+        &self.vocab
+    }
+
+    fn block_size(&self) -> usize {
+        self.block_size
+    }
+
+    fn logits(&self, context: &[usize]) -> Vec<MVal> {
+        let emb = self.embedding.forward(context);
+        self.output.forward(self.hidden.forward(emb))
+    }
+}
@@ -0,0 +1,146 @@
+use std::fmt::Display;
+
+use micrograd::cross_entropy;
+use rand::{rngs::StdRng, seq::SliceRandom, SeedableRng};
+
+use crate::{model::LanguageModel, vocab::build_dataset};
+
+/*
+How are the language models compared?
+    - Every model returns the logits of the next token, so all of them are evaluated the same way,
+      by the average negative log likelihood(NLL) of the next tokens of the words. It is the same as
+      the cross entropy loss used in training, lower is better.
+
+    - Perplexity is exp(NLL). It can be read as the number of tokens the model is choosing from at
+      each step, so a model guessing uniformly among 27 tokens has the perplexity 27.
+
+    - Bits per character is NLL / ln(2), the number of bits needed on average to encode each
+      character of the words using the probabilities of the model.
+
+    - Every character of the words and the END token after each word counts as a character.
+
+How to Use it?
+    - Build the Vocab from all the words, so that the characters of the val and test words are
+      known to the model, then split the words and train the model only on the train words.
+            `let vocab = Vocab::from_words(&words);
+             let splits = split_words(&words, 0.8, 0.1, 42);
+             let model = Bigram::new(vocab, &splits.train, 1.0);
+             println!("{}", report(&model, &splits));`
+*/
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Evaluation {
+    // number of predicted tokens.
+    n_tokens: usize,
+    // average negative log likelihood of the tokens.
+    nll: f64,
+}
+
+impl Evaluation {
+    pub fn n_tokens(&self) -> usize {
+        self.n_tokens
+    }
+
+    pub fn nll(&self) -> f64 {
+        self.nll
+    }
+
+    pub fn perplexity(&self) -> f64 {
+        self.nll.exp()
+    }
+
+    pub fn bits_per_char(&self) -> f64 {
+        self.nll / std::f64::consts::LN_2
+    }
+}
+
+/// evaluates the model on predicting each character of the words and the END token after them.
+/// Panics if a word has a character that is not in the Vocab of the model.
+pub fn evaluate<M: LanguageModel + ?Sized>(model: &M, words: &[String]) -> Evaluation {
+    let (contexts, targets) = build_dataset(words, model.vocab(), model.block_size());
+    let total: f64 = contexts
+        .iter()
+        .zip(&targets)
+        .map(|(context, &target)| cross_entropy(&model.logits(context), target).get())
+        .sum();
+    Evaluation {
+        n_tokens: targets.len(),
+        nll: total / targets.len().max(1) as f64,
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct Splits {
+    pub train: Vec<String>,
+    pub val: Vec<String>,
+    pub test: Vec<String>,
+}
+
+/// shuffles the words with the seed and splits them into train, val and the rest as test.
+/// train_fraction, val_fraction - fraction of the words in the train and val splits.
+pub fn split_words(words: &[String], train_fraction: f64, val_fraction: f64, seed: u64) -> Splits {
+    let mut shuffled = words.to_vec();
+    shuffled.shuffle(&mut StdRng::seed_from_u64(seed));
+    let n_train = ((words.len() as f64 * train_fraction) as usize).min(words.len());
+    let n_val = ((words.len() as f64 * val_fraction) as usize).min(words.len() - n_train);
+    let test = shuffled.split_off(n_train + n_val);
+    let val = shuffled.split_off(n_train);
+    Splits {
+        train: shuffled,
+        val,
+        test,
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Report {
+    // name of each split with its evaluation.
+    rows: Vec<(String, Evaluation)>,
+}
+
+impl Report {
+    pub fn rows(&self) -> &[(String, Evaluation)] {
+        &self.rows
+    }
+
+    /// evaluation of the split with the name, e.g. "val".
+    pub fn get(&self, split: &str) -> Option<&Evaluation> {
+        self.rows.iter().find(|(s, _)| s == split).map(|(_, e)| e)
+    }
+}
+
+/// evaluates the model on each of the train, val and test splits. Empty splits are skipped.
+pub fn report<M: LanguageModel + ?Sized>(model: &M, splits: &Splits) -> Report {
+    let rows = [
+        ("train", &splits.train),
+        ("val", &splits.val),
+        ("test", &splits.test),
+    ]
+    .into_iter()
+    .filter(|(_, words)| !words.is_empty())
+    .map(|(name, words)| (name.to_string(), evaluate(model, words)))
+    .collect();
+    Report { rows }
+}
+
+impl Display for Report {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(
+            f,
+            "{:<8}{:>10}{:>10}{:>12}{:>10}",
+            "split", "tokens", "nll", "perplexity", "bpc"
+        )?;
+        for (split, e) in &self.rows {
+            writeln!(
+                f,
+                "{:<8}{:>10}{:>10.4}{:>12.4}{:>10.4}",
+                split,
+                e.n_tokens(),
+                e.nll(),
+                e.perplexity(),
+                e.bits_per_char()
+            )?;
+        }
+        Ok(())
+    }
+}
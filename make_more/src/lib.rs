@@ -1 +1,15 @@
+mod bigram;
+mod char_mlp;
+pub mod eval;
+mod model;
+pub mod sampling;
+#[cfg(test)]
+mod tests;
+pub mod vocab;
 
+pub use bigram::Bigram;
+pub use char_mlp::CharMLP;
+pub use eval::{evaluate, report, split_words, Evaluation, Report, Splits};
+pub use model::LanguageModel;
+pub use sampling::SamplingOptions;
+pub use vocab::{build_dataset, Vocab};
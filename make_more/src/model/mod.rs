@@ -0,0 +1,64 @@
+use micrograd::MVal;
+use rand::Rng;
+
+use crate::{
+    sampling::{self, SamplingOptions},
+    vocab::Vocab,
+};
+
+/*
+What is a LanguageModel?
+    - A model that returns the logits of the next token for the context of the previous
+      block_size tokens. Both the count based Bigram and the neural CharMLP implement it.
+
+    - Words are generated by starting from the context filled with the END token and sampling the
+      next token until the END token is sampled or the word reaches max_len characters.
+      The words are sampled with the given RNG, so pass the same RNG to the next calls to get
+      new words, or a new RNG with the same seed to get the same words again.
+            `let mut rng = StdRng::seed_from_u64(2147483647);
+             let words = model.generate(10, 20, &mut rng);`
+*/
+
+pub trait LanguageModel {
+    fn vocab(&self) -> &Vocab;
+
+    /// number of previous tokens the model looks at to predict the next token.
+    fn block_size(&self) -> usize;
+
+    /// logits of each token of the Vocab being the next token of the context.
+    /// context - the previous block_size tokens.
+    fn logits(&self, context: &[usize]) -> Vec<MVal>;
+
+    /// generates n words of at most max_len characters with the default sampling options.
+    fn generate(&self, n: usize, max_len: usize, rng: &mut impl Rng) -> Vec<String> {
+        self.generate_custom(n, max_len, &SamplingOptions::default(), rng)
+    }
+
+    /// Same as generate but with custom temperature, top_k and top_p.
+    fn generate_custom(
+        &self,
+        n: usize,
+        max_len: usize,
+        options: &SamplingOptions,
+        rng: &mut impl Rng,
+    ) -> Vec<String> {
+        (0..n)
+            .map(|_| {
+                let mut context = vec![0; self.block_size()];
+                let mut word = String::new();
+                for _ in 0..max_len {
+                    let token = sampling::sample(&self.logits(&context), options, rng);
+                    if token == 0 {
+                        break;
+                    }
+                    word.push(self.vocab().decode(token));
+                    if !context.is_empty() {
+                        context.remove(0);
+                        context.push(token);
+                    }
+                }
+                word
+            })
+            .collect()
+    }
+}
@@ -0,0 +1,117 @@
+use micrograd::{argmax, metrics, softmax, MVal};
+use rand::Rng;
+
+/*
+How is the next token sampled?
+    - The model returns a logit for each token. The logits are divided by the temperature and
+      converted to probabilities with softmax. Temperature below 1 makes the likely tokens more
+      likely(less random words) and above 1 makes the distribution flatter(more random words).
+      Temperature 0 always picks the most likely token.
+
+    - top_k keeps only the k most likely tokens and top_p(nucleus) keeps the smallest set of
+      most likely tokens whose total probability is at least p. The probabilities of the
+      other tokens are set to 0 and the rest are renormalised.
+
+    - Finally the token is drawn from the probabilities(multinomial sampling) with the RNG given
+      by the caller. A seeded RNG generates the same words for the same seed, and reusing the RNG
+      for the next calls continues the sequence instead of repeating the same words.
+*/
+
+#[derive(Debug, Clone)]
+pub struct SamplingOptions {
+    pub temperature: f64,
+    pub top_k: Option<usize>,
+    pub top_p: Option<f64>,
+}
+
+impl Default for SamplingOptions {
+    fn default() -> Self {
+        Self {
+            temperature: 1.0,
+            top_k: None,
+            top_p: None,
+        }
+    }
+}
+
+/// softmax of the logits divided by the temperature.
+/// temperature 0(or below) gives the probability 1 to the highest logit.
+pub fn probs(logits: &[MVal], temperature: f64) -> Vec<f64> {
+    if temperature <= 0.0 {
+        let best = argmax(&metrics::values(logits));
+        return (0..logits.len())
+            .map(|i| if i == best { 1.0 } else { 0.0 })
+            .collect();
+    }
+    let scaled: Vec<MVal> = logits.iter().map(|l| l.clone() / temperature).collect();
+    metrics::values(&softmax(&scaled))
+}
+
+/// keeps the probabilities of the k most likely tokens and renormalises them.
+/// Tokens with the same probability are kept in the order of their index.
+pub fn top_k(probs: &[f64], k: usize) -> Vec<f64> {
+    let order = sorted_desc(probs);
+    keep(probs, &order[..k.max(1).min(probs.len())])
+}
+
+/// keeps the probabilities of the smallest set of most likely tokens whose
+/// total probability is at least p and renormalises them. The most likely token is always kept
+/// and p of 1 or more keeps all of them. Ties are broken like top_k.
+pub fn top_p(probs: &[f64], p: f64) -> Vec<f64> {
+    let order = sorted_desc(probs);
+    let total: f64 = probs.iter().sum();
+    let mut cumulative = 0.0;
+    let mut n_keep = 0;
+    for &i in &order {
+        cumulative += probs[i];
+        n_keep += 1;
+        if cumulative >= p * total {
+            break;
+        }
+    }
+    keep(probs, &order[..n_keep])
+}
+
+/// draws a token with the given probabilities. The probabilities don't need to sum to 1.
+pub fn multinomial(probs: &[f64], rng: &mut impl Rng) -> usize {
+    let total: f64 = probs.iter().sum();
+    let mut r = rng.gen::<f64>() * total;
+    for (i, &p) in probs.iter().enumerate() {
+        if r < p {
+            return i;
+        }
+        r -= p;
+    }
+    // r can be left over due to the rounding errors, so return the last possible token.
+    probs.iter().rposition(|&p| p > 0.0).unwrap_or(0)
+}
+
+/// samples the next token from the logits using the temperature, top_k and top_p of the options.
+pub fn sample(logits: &[MVal], options: &SamplingOptions, rng: &mut impl Rng) -> usize {
+    let mut probs = probs(logits, options.temperature);
+    if let Some(k) = options.top_k {
+        probs = top_k(&probs, k);
+    }
+    if let Some(p) = options.top_p {
+        probs = top_p(&probs, p);
+    }
+    multinomial(&probs, rng)
+}
+
+/// indices of the probabilities from the highest to the lowest. The sort is stable, so the
+/// equal probabilities stay in the order of their index.
+fn sorted_desc(probs: &[f64]) -> Vec<usize> {
+    let mut order: Vec<usize> = (0..probs.len()).collect();
+    order.sort_by(|&i, &j| probs[j].total_cmp(&probs[i]));
+    order
+}
+
+/// keeps the probabilities of the indices and renormalises them.
+fn keep(probs: &[f64], indices: &[usize]) -> Vec<f64> {
+    let total: f64 = indices.iter().map(|&i| probs[i]).sum();
+    let mut out = vec![0.0; probs.len()];
+    for &i in indices {
+        out[i] = if total > 0.0 { probs[i] / total } else { 0.0 };
+    }
+    out
+}
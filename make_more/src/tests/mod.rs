@@ -0,0 +1,38 @@
+mod sampling;
+
+use rand::{rngs::StdRng, SeedableRng};
+
+use crate::{Bigram, LanguageModel, SamplingOptions, Vocab};
+
+fn bigram() -> Bigram {
+    let words: Vec<String> = [
+        "emma", "olivia", "ava", "isabella", "sophia", "mia", "amelia",
+    ]
+    .iter()
+    .map(|w| w.to_string())
+    .collect();
+    Bigram::new(Vocab::from_words(&words), &words, 1.0)
+}
+
+#[test]
+fn generate_continues_the_rng_across_calls() {
+    let model = bigram();
+    let mut rng = StdRng::seed_from_u64(42);
+    let first = model.generate(10, 20, &mut rng);
+    let second = model.generate(10, 20, &mut rng);
+    assert_ne!(first, second);
+}
+
+#[test]
+fn generate_repeats_the_words_of_the_same_seed() {
+    let model = bigram();
+    let options = SamplingOptions {
+        temperature: 0.7,
+        top_k: Some(5),
+        ..Default::default()
+    };
+    let words = model.generate_custom(10, 20, &options, &mut StdRng::seed_from_u64(42));
+    let again = model.generate_custom(10, 20, &options, &mut StdRng::seed_from_u64(42));
+    assert_eq!(words, again);
+    assert!(words.iter().all(|w| w.chars().count() <= 20));
+}
@@ -0,0 +1,78 @@
+use rand::{rngs::StdRng, SeedableRng};
+
+use crate::sampling::{multinomial, top_k, top_p};
+
+fn assert_close(actual: &[f64], expected: &[f64]) {
+    assert_eq!(actual.len(), expected.len());
+    for (a, e) in actual.iter().zip(expected) {
+        assert!((a - e).abs() < 1e-12, "{actual:?} != {expected:?}");
+    }
+}
+
+const PROBS: [f64; 4] = [0.1, 0.4, 0.2, 0.3];
+
+#[test]
+fn top_k_keeps_the_k_most_likely() {
+    assert_close(&top_k(&PROBS, 2), &[0.0, 4.0 / 7.0, 0.0, 3.0 / 7.0]);
+    assert_close(&top_k(&PROBS, 1), &[0.0, 1.0, 0.0, 0.0]);
+    // at least one token is kept and k above the number of tokens keeps all.
+    assert_close(&top_k(&PROBS, 0), &[0.0, 1.0, 0.0, 0.0]);
+    assert_close(&top_k(&PROBS, 10), &PROBS);
+}
+
+#[test]
+fn top_k_breaks_ties_by_index() {
+    assert_close(&top_k(&[0.25; 4], 2), &[0.5, 0.5, 0.0, 0.0]);
+    assert_close(&top_k(&[0.1, 0.3, 0.3, 0.3], 2), &[0.0, 0.5, 0.5, 0.0]);
+}
+
+#[test]
+fn top_p_keeps_the_smallest_nucleus() {
+    // 0.4 + 0.3 is the first cumulative probability reaching 0.6.
+    assert_close(&top_p(&PROBS, 0.6), &[0.0, 4.0 / 7.0, 0.0, 3.0 / 7.0]);
+    assert_close(&top_p(&PROBS, 0.7), &[0.0, 4.0 / 7.0, 0.0, 3.0 / 7.0]);
+    // the most likely token is always kept.
+    assert_close(&top_p(&PROBS, 0.0), &[0.0, 1.0, 0.0, 0.0]);
+}
+
+#[test]
+fn top_p_of_one_or_more_keeps_all() {
+    assert_close(&top_p(&PROBS, 1.0), &PROBS);
+    assert_close(&top_p(&PROBS, 1.5), &PROBS);
+    // unnormalised probabilities are renormalised.
+    assert_close(&top_p(&[1.0, 4.0, 2.0, 3.0], 1.0), &PROBS);
+}
+
+#[test]
+fn top_p_breaks_ties_by_index() {
+    assert_close(&top_p(&[0.2, 0.3, 0.2, 0.3], 0.5), &[0.0, 0.5, 0.0, 0.5]);
+    assert_close(&top_p(&[0.25; 4], 0.5), &[0.5, 0.5, 0.0, 0.0]);
+}
+
+#[test]
+fn multinomial_follows_the_probabilities() {
+    let probs = [0.2, 0.5, 0.3, 0.0];
+    let draws = 20_000;
+    let mut rng = StdRng::seed_from_u64(42);
+    let mut counts = [0; 4];
+    for _ in 0..draws {
+        counts[multinomial(&probs, &mut rng)] += 1;
+    }
+    let freqs: Vec<f64> = counts.iter().map(|&c| c as f64 / draws as f64).collect();
+    for (f, p) in freqs.iter().zip(probs) {
+        assert!((f - p).abs() < 0.02, "{freqs:?} != {probs:?}");
+    }
+    assert_eq!(counts[3], 0);
+}
+
+#[test]
+fn multinomial_is_reproducible_with_the_seed() {
+    let draw = |seed| {
+        let mut rng = StdRng::seed_from_u64(seed);
+        (0..20)
+            .map(|_| multinomial(&[2.0, 5.0, 3.0], &mut rng))
+            .collect::<Vec<usize>>()
+    };
+    assert_eq!(draw(7), draw(7));
+    assert_ne!(draw(7), draw(8));
+}
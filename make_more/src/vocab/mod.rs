@@ -0,0 +1,82 @@
+use std::collections::BTreeSet;
+
+/*
+What is Vocab?
+    - makemore models generate words character by character. Each character of the words is a
+      token and Vocab maps the characters to the token ids and back.
+
+    - The token 0 is the special '.' character that marks both the start and the end of a word.
+      So the model learns which characters start a word by predicting the next token of the
+      context filled with '.' and learns when to stop by predicting the '.' token.
+
+    - The training samples are (context, target) pairs where the context is the previous
+      block_size tokens and the target is the next token. For the word "emma" with block_size 3
+            `... -> e, ..e -> m, .em -> m, emm -> a, mma -> .`
+*/
+
+/// marks the start and the end of the words.
+pub const END: char = '.';
+
+#[derive(Debug, Clone)]
+pub struct Vocab {
+    // index of each character is its token id.
+    chars: Vec<char>,
+}
+
+impl Vocab {
+    /// creates the Vocab of all the characters of the words in sorted order after the END token.
+    pub fn from_words(words: &[String]) -> Self {
+        let chars: BTreeSet<char> = words
+            .iter()
+            .flat_map(|w| w.chars())
+            .filter(|&c| c != END)
+            .collect();
+        Self {
+            chars: std::iter::once(END).chain(chars).collect(),
+        }
+    }
+
+    /// number of tokens including the END token.
+    pub fn len(&self) -> usize {
+        self.chars.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.chars.is_empty()
+    }
+
+    pub fn encode(&self, c: char) -> Option<usize> {
+        self.chars.iter().position(|&v| v == c)
+    }
+
+    /// Panics if the token is not less than the length of the Vocab.
+    pub fn decode(&self, token: usize) -> char {
+        self.chars[token]
+    }
+}
+
+/// returns the contexts of block_size tokens and the next token of each context for all the words.
+/// Panics if a word has a character that is not in the Vocab.
+pub fn build_dataset(
+    words: &[String],
+    vocab: &Vocab,
+    block_size: usize,
+) -> (Vec<Vec<usize>>, Vec<usize>) {
+    let mut contexts = vec![];
+    let mut targets = vec![];
+    for word in words {
+        let mut context = vec![0; block_size];
+        for c in word.chars().chain(std::iter::once(END)) {
+            let token = vocab
+                .encode(c)
+                .unwrap_or_else(|| panic!("character {c:?} of {word:?} is not in the vocab"));
+            contexts.push(context.clone());
+            targets.push(token);
+            if block_size > 0 {
+                context.remove(0);
+                context.push(token);
+            }
+        }
+    }
+    (contexts, targets)
+}
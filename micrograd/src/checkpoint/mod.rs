@@ -0,0 +1,202 @@
+use std::{collections::HashMap, fs, path::Path};
+
+use rand_chacha::rand_core::SeedableRng;
+pub use rand_chacha::ChaCha8Rng;
+
+use crate::{
+    error::{Error, Result},
+    nn::MLP,
+    optim::{LinearDecay, Sgd},
+    value::{data_type::Float, MVal},
+};
+
+/*
+What is stored in a Checkpoint?
+    - Everything that the next step of the training depends on. The weights and biases of the MLP,
+      the velocity of the Sgd optimizer, the step of the LinearDecay scheduler, the current
+      step/epoch of the training loop and the state of the ChaCha8Rng used for sampling the batches.
+
+    - Floating point numbers are stored with their exact bits(as hex), so the restored training
+      computes exactly the same numbers as the training that was never interrupted.
+
+    - The RNG has to be a ChaCha8Rng, because its state can be read and restored exactly,
+      unlike the thread_rng.
+
+How to Use it?
+    - Save the checkpoint in the training loop after the update of the step.
+            `Checkpoint::new(&mut model, &optimizer, &scheduler, &rng, step, epoch).save(path)?;`
+
+    - To resume, create the model, optimizer and scheduler with the same configuration,
+      restore the checkpoint and continue the training loop from the next step.
+            `let checkpoint = Checkpoint::load(path)?;
+             let mut rng = checkpoint.restore(&mut model, &mut optimizer, &mut scheduler)?;
+             for step in checkpoint.step() + 1..total_steps { ... }`
+*/
+
+const HEADER: &str = "micrograd-checkpoint 1";
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Checkpoint {
+    step: usize,
+    epoch: usize,
+    // in the same order as MLP::parameters
+    weights: Vec<f64>,
+    velocity: Vec<f64>,
+    scheduler_step: usize,
+    rng_seed: [u8; 32],
+    rng_stream: u64,
+    rng_word_pos: u128,
+}
+
+impl Checkpoint {
+    /// captures the current state of the training.
+    /// step, epoch - the last completed step and epoch of the training loop.
+    pub fn new<F: Float>(
+        model: &mut MLP<F>,
+        optimizer: &Sgd,
+        scheduler: &LinearDecay,
+        rng: &ChaCha8Rng,
+        step: usize,
+        epoch: usize,
+    ) -> Self {
+        Self {
+            step,
+            epoch,
+            weights: model
+                .parameters()
+                .iter()
+                .map(|p| p.get().to_f64())
+                .collect(),
+            velocity: optimizer.velocity().to_vec(),
+            scheduler_step: scheduler.current(),
+            rng_seed: rng.get_seed(),
+            rng_stream: rng.get_stream(),
+            rng_word_pos: rng.get_word_pos(),
+        }
+    }
+
+    pub fn step(&self) -> usize {
+        self.step
+    }
+
+    pub fn epoch(&self) -> usize {
+        self.epoch
+    }
+
+    /// restores the weights of the model, the optimizer and the scheduler state
+    /// and returns the RNG in the same state as it was captured.
+    /// returns error if the model doesn't have the same number of parameters as the checkpoint
+    /// or the velocity of the checkpoint doesn't match the parameters.
+    pub fn restore<F: Float>(
+        &self,
+        model: &mut MLP<F>,
+        optimizer: &mut Sgd,
+        scheduler: &mut LinearDecay,
+    ) -> Result<ChaCha8Rng> {
+        let params = model.parameters();
+        if params.len() != self.weights.len() {
+            return Err(Error::Checkpoint(format!(
+                "expecting a model with {} parameters but got {}",
+                self.weights.len(),
+                params.len()
+            )));
+        }
+        // empty velocity means the optimizer didn't take a step yet.
+        if !self.velocity.is_empty() && self.velocity.len() != params.len() {
+            return Err(Error::Checkpoint(format!(
+                "expecting the velocity of {} parameters but got {}",
+                params.len(),
+                self.velocity.len()
+            )));
+        }
+        for (p, &w) in params.iter().zip(&self.weights) {
+            p.set(MVal::new_typed(F::from_f64(w)));
+        }
+        optimizer.set_velocity(self.velocity.clone());
+        scheduler.set_current(self.scheduler_step);
+
+        let mut rng = ChaCha8Rng::from_seed(self.rng_seed);
+        rng.set_stream(self.rng_stream);
+        rng.set_word_pos(self.rng_word_pos);
+        Ok(rng)
+    }
+
+    /// writes the checkpoint as a text file with one `key value` entry per line.
+    pub fn save<P: AsRef<Path>>(&self, path: P) -> Result<()> {
+        let seed: String = self.rng_seed.iter().map(|b| format!("{b:02x}")).collect();
+        let content = format!(
+            "{HEADER}\nstep {}\nepoch {}\nscheduler_step {}\nrng_seed {}\nrng_stream {}\nrng_word_pos {}\nweights {}\nvelocity {}\n",
+            self.step,
+            self.epoch,
+            self.scheduler_step,
+            seed,
+            self.rng_stream,
+            self.rng_word_pos,
+            encode(&self.weights),
+            encode(&self.velocity),
+        );
+        fs::write(path, content).map_err(|err| Error::Checkpoint(err.to_string()))
+    }
+
+    /// reads the checkpoint written by save.
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<Self> {
+        let content = fs::read_to_string(path).map_err(|err| Error::Checkpoint(err.to_string()))?;
+        let mut lines = content.lines();
+        if lines.next() != Some(HEADER) {
+            return Err(Error::Checkpoint(
+                "missing the checkpoint header".to_string(),
+            ));
+        }
+        let entries: HashMap<&str, &str> = lines
+            .map(|line| line.split_once(' ').unwrap_or((line, "")))
+            .collect();
+        let entry = |key: &str| {
+            entries
+                .get(key)
+                .copied()
+                .ok_or_else(|| Error::Checkpoint(format!("missing {key}")))
+        };
+        let invalid = |key: &str| Error::Checkpoint(format!("invalid {key}"));
+        let number = |key: &str| entry(key)?.parse().map_err(|_| invalid(key));
+
+        let seed = entry("rng_seed")?;
+        let mut rng_seed = [0; 32];
+        if seed.len() != 64 || !seed.is_ascii() {
+            return Err(invalid("rng_seed"));
+        }
+        for (i, byte) in rng_seed.iter_mut().enumerate() {
+            *byte =
+                u8::from_str_radix(&seed[i * 2..i * 2 + 2], 16).map_err(|_| invalid("rng_seed"))?;
+        }
+
+        Ok(Self {
+            step: number("step")?,
+            epoch: number("epoch")?,
+            weights: decode(entry("weights")?).ok_or_else(|| invalid("weights"))?,
+            velocity: decode(entry("velocity")?).ok_or_else(|| invalid("velocity"))?,
+            scheduler_step: number("scheduler_step")?,
+            rng_seed,
+            rng_stream: entry("rng_stream")?
+                .parse()
+                .map_err(|_| invalid("rng_stream"))?,
+            rng_word_pos: entry("rng_word_pos")?
+                .parse()
+                .map_err(|_| invalid("rng_word_pos"))?,
+        })
+    }
+}
+
+/// space separated bits of each value in hex.
+fn encode(values: &[f64]) -> String {
+    values
+        .iter()
+        .map(|v| format!("{:016x}", v.to_bits()))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+fn decode(text: &str) -> Option<Vec<f64>> {
+    text.split_whitespace()
+        .map(|v| u64::from_str_radix(v, 16).ok().map(f64::from_bits))
+        .collect()
+}
@@ -0,0 +1,56 @@
+use std::fmt::Display;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Error {
+    /// length of the input instance is not equal to the number of weights of the Neuron.
+    InputLength { expected: usize, actual: usize },
+    /// checkpoint file can't be read, written or parsed, or it doesn't match the model.
+    Checkpoint(String),
+    /// token id is not less than the number of tokens of the Embedding.
+    TokenOutOfRange { token: usize, n_tokens: usize },
+    /// a node produced inf or NaN in the forward(data) or backward(gradient) pass.
+    NonFinite {
+        stage: &'static str,
+        label: String,
+        operator: String,
+        value: f64,
+    },
+    /// backward is called on a graph whose operands were dropped by a previous backward.
+    GraphFreed,
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::InputLength { expected, actual } => write!(
+                f,
+                "expecting input of length {} but got input of length {}",
+                expected, actual
+            ),
+            Error::Checkpoint(msg) => write!(f, "invalid checkpoint: {}", msg),
+            Error::TokenOutOfRange { token, n_tokens } => write!(
+                f,
+                "token {} is out of range for the embedding of {} tokens",
+                token, n_tokens
+            ),
+            Error::NonFinite {
+                stage,
+                label,
+                operator,
+                value,
+            } => write!(
+                f,
+                "{} pass produced {} at the {} node {}",
+                stage, value, operator, label
+            ),
+            Error::GraphFreed => write!(
+                f,
+                "trying to backward through the graph a second time, but it was freed by the first backward. Set retain_graph to true to backward more than once"
+            ),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+pub type Result<T> = std::result::Result<T, Error>;
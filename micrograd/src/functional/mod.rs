@@ -0,0 +1,90 @@
+use crate::{
+    error::Result,
+    value::{data_type::Float, BackwardOptions, MVal},
+};
+
+/*
+What are the functional helpers?
+    - They differentiate a closure that builds an MVal expression from its inputs. The helpers
+      create the input Values, build the graph and compute the gradients, so the caller
+      doesn't need to manage the graph state like zero_grad and backward.
+
+    - The closure is called with the input Values and the inputs are given as plain numbers.
+            `let (y, grads) = functional::value_and_grad(|xs| xs[0].clone() * xs[1].clone(), &[1.0, 2.0])?;`
+
+    - Values captured by the closure(like the parameters of a model) are part of the graph too,
+      so they will get their gradient like after `zero_grad` and `backward`.
+
+    - The helpers return the error of backward, like when the closure returns a Value whose graph
+      was already freed by an other backward.
+*/
+
+/// returns the output of f at the inputs and the gradient of the output w.r.t each input.
+pub fn value_and_grad<F, Func>(f: Func, inputs: &[F]) -> Result<(F, Vec<F>)>
+where
+    F: Float,
+    Func: Fn(&[MVal<F>]) -> MVal<F>,
+{
+    let xs: Vec<MVal<F>> = inputs.iter().map(|&x| MVal::new_typed(x)).collect();
+    let mut out = f(&xs);
+    out.zero_grad();
+    out.backward()?;
+    Ok((out.get(), xs.iter().map(|x| x.grad()).collect()))
+}
+
+/// Same as value_and_grad but returns only the gradients.
+pub fn grad<F, Func>(f: Func, inputs: &[F]) -> Result<Vec<F>>
+where
+    F: Float,
+    Func: Fn(&[MVal<F>]) -> MVal<F>,
+{
+    Ok(value_and_grad(f, inputs)?.1)
+}
+
+/// returns the matrix of gradients of the vector valued f at the inputs, where
+/// jacobian[i][j] is the gradient of output_i w.r.t input_j.
+pub fn jacobian<F, Func>(f: Func, inputs: &[F]) -> Result<Vec<Vec<F>>>
+where
+    F: Float,
+    Func: Fn(&[MVal<F>]) -> Vec<MVal<F>>,
+{
+    let xs: Vec<MVal<F>> = inputs.iter().map(|&x| MVal::new_typed(x)).collect();
+    // the outputs can share a part of the graph, so it is retained for the next outputs.
+    let options = BackwardOptions { retain_graph: true };
+    f(&xs)
+        .into_iter()
+        .map(|mut out| {
+            // zero_grad only resets the Values the output is depending on,
+            // so the inputs are reset separately to clear the gradients of the previous output.
+            for x in &xs {
+                x.set_grad(MVal::default());
+            }
+            out.zero_grad();
+            out.backward_custom(&options)?;
+            Ok(xs.iter().map(|x| x.grad()).collect())
+        })
+        .collect()
+}
+
+/// returns the matrix of second order gradients of f at the inputs, where
+/// hessian[i][j] is the gradient of (df/d input_i) w.r.t input_j.
+/// It builds the graph of each first order gradient, so use it only for small problems.
+/// returns error if f returns a Value whose graph was freed by backward_custom.
+pub fn hessian<F, Func>(f: Func, inputs: &[F]) -> Result<Vec<Vec<F>>>
+where
+    F: Float,
+    Func: Fn(&[MVal<F>]) -> MVal<F>,
+{
+    let xs: Vec<MVal<F>> = inputs.iter().map(|&x| MVal::new_typed(x)).collect();
+    let out = f(&xs);
+    out.backward_graph(&xs)?
+        .iter()
+        .map(|grad| {
+            Ok(grad
+                .backward_graph(&xs)?
+                .iter()
+                .map(|grad2| grad2.get())
+                .collect())
+        })
+        .collect()
+}
@@ -1,5 +1,26 @@
+pub mod checkpoint;
+mod error;
+pub mod functional;
+pub mod metrics;
 mod nn;
+pub mod optim;
+pub mod profile;
+mod sync;
+mod tape;
+#[cfg(test)]
+mod tests;
 mod value;
 
-pub use nn::{ActivationType, MLP};
-pub use value::MVal;
+pub use error::{Error, Result};
+pub use nn::{
+    classify::{argmax, cross_entropy, softmax},
+    embedding::Embedding,
+    ActivationType, Layer, Neuron, MLP,
+};
+pub use sync::{nn::AMLP, parallel::parallel_backward, AVal};
+pub use tape::{nn::TapeMLP, Tape, Var};
+pub use value::{
+    anomaly::{detect_anomaly, AnomalyMode},
+    data_type::Float,
+    BackwardOptions, MVal,
+};
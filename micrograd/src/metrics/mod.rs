@@ -0,0 +1,209 @@
+use crate::value::{data_type::Float, MVal};
+
+/*
+What are metrics?
+    - Metrics are used to evaluate a trained model. Unlike the loss they are not part of
+      the MVal graph, so they operate on plain f64 values extracted from the model outputs.
+            `let scores = metrics::values(&model.forward(v));`
+
+    - Binary classifiers in this crate predict a score and use 1 and -1 as the labels.
+      `binary_labels` converts the scores to labels before computing accuracy, precision etc.
+
+    - Functions taking both predictions and actual values expect them to have the same length
+      and will panic otherwise, since comparing lists of different length is always a bug.
+*/
+
+/// extracts the data of each MVal output as f64.
+pub fn values<F: Float>(outs: &[MVal<F>]) -> Vec<f64> {
+    outs.iter().map(|o| o.get().to_f64()).collect()
+}
+
+/// returns 1 for scores above the threshold else -1.
+pub fn binary_labels(scores: &[f64], threshold: f64) -> Vec<f64> {
+    scores
+        .iter()
+        .map(|&s| if s > threshold { 1.0 } else { -1.0 })
+        .collect()
+}
+
+fn check_len(pred: usize, actual: usize) {
+    assert_eq!(
+        pred, actual,
+        "expecting same number of predictions and actual values"
+    );
+}
+
+/// fraction of predicted labels that are equal to the actual labels.
+/// works for both binary(1, -1) and multi class(0, 1, 2,...) labels.
+pub fn accuracy<T: PartialEq>(pred: &[T], actual: &[T]) -> f64 {
+    check_len(pred.len(), actual.len());
+    if pred.is_empty() {
+        return 0.0;
+    }
+    let correct = pred.iter().zip(actual).filter(|(p, a)| p == a).count();
+    correct as f64 / pred.len() as f64
+}
+
+#[derive(Debug, Clone)]
+pub struct PrecisionRecall {
+    pub precision: f64,
+    pub recall: f64,
+    pub f1: f64,
+}
+
+/// precision, recall and F1 score of the `positive` label.
+/// precision = tp/(tp + fp), recall = tp/(tp + fn) and f1 is their harmonic mean.
+/// Each of them will be 0 when its denominator is 0.
+pub fn precision_recall_f1(pred: &[f64], actual: &[f64], positive: f64) -> PrecisionRecall {
+    check_len(pred.len(), actual.len());
+    let (mut tp, mut fp, mut fn_) = (0.0, 0.0, 0.0);
+    for (&p, &a) in pred.iter().zip(actual) {
+        match (p == positive, a == positive) {
+            (true, true) => tp += 1.0,
+            (true, false) => fp += 1.0,
+            (false, true) => fn_ += 1.0,
+            (false, false) => {}
+        }
+    }
+    let ratio = |num: f64, den: f64| if den > 0.0 { num / den } else { 0.0 };
+    let precision = ratio(tp, tp + fp);
+    let recall = ratio(tp, tp + fn_);
+    PrecisionRecall {
+        precision,
+        recall,
+        f1: ratio(2.0 * precision * recall, precision + recall),
+    }
+}
+
+/// matrix[i][j] is the number of samples with actual class i that are predicted as class j.
+/// Panics if any predicted or actual class is not less than n_classes.
+pub fn confusion_matrix(pred: &[usize], actual: &[usize], n_classes: usize) -> Vec<Vec<usize>> {
+    check_len(pred.len(), actual.len());
+    let mut matrix = vec![vec![0; n_classes]; n_classes];
+    for (&p, &a) in pred.iter().zip(actual) {
+        assert!(
+            p < n_classes && a < n_classes,
+            "expecting classes less than {n_classes} but got predicted {p} and actual {a}"
+        );
+        matrix[a][p] += 1;
+    }
+    matrix
+}
+
+/// fraction of the samples of each class that are predicted correctly(recall of each class).
+/// It is 0 for the classes without any samples. Panics like confusion_matrix.
+pub fn per_class_accuracy(pred: &[usize], actual: &[usize], n_classes: usize) -> Vec<f64> {
+    confusion_matrix(pred, actual, n_classes)
+        .iter()
+        .enumerate()
+        .map(|(class, row)| {
+            let total: usize = row.iter().sum();
+            if total == 0 {
+                0.0
+            } else {
+                row[class] as f64 / total as f64
+            }
+        })
+        .collect()
+}
+
+/// area under the ROC curve for the `positive` label using the predicted scores.
+/// It is the probability that a random positive sample gets higher score than
+/// a random negative sample(ties count as half). Returns 0.5 when one of the classes is missing.
+pub fn roc_auc(scores: &[f64], actual: &[f64], positive: f64) -> f64 {
+    check_len(scores.len(), actual.len());
+    let mut order: Vec<usize> = (0..scores.len()).collect();
+    order.sort_by(|&i, &j| scores[i].total_cmp(&scores[j]));
+
+    // sum of the ranks(starting from 1) of positive samples. tied scores share their average rank.
+    let mut pos_rank_sum = 0.0;
+    let mut start = 0;
+    while start < order.len() {
+        let mut end = start;
+        while end + 1 < order.len() && scores[order[end + 1]] == scores[order[start]] {
+            end += 1;
+        }
+        let avg_rank = (start + end) as f64 / 2.0 + 1.0;
+        for &i in &order[start..=end] {
+            if actual[i] == positive {
+                pos_rank_sum += avg_rank;
+            }
+        }
+        start = end + 1;
+    }
+
+    let n_pos = actual.iter().filter(|&&a| a == positive).count() as f64;
+    let n_neg = actual.len() as f64 - n_pos;
+    if n_pos == 0.0 || n_neg == 0.0 {
+        return 0.5;
+    }
+    (pos_rank_sum - n_pos * (n_pos + 1.0) / 2.0) / (n_pos * n_neg)
+}
+
+/// binary cross entropy. probs are the predicted probabilities of the `positive` label.
+/// probabilities are clipped to [1e-15, 1-1e-15] to avoid log(0).
+pub fn log_loss(probs: &[f64], actual: &[f64], positive: f64) -> f64 {
+    check_len(probs.len(), actual.len());
+    if probs.is_empty() {
+        return 0.0;
+    }
+    let eps = 1e-15;
+    let total: f64 = probs
+        .iter()
+        .zip(actual)
+        .map(|(&p, &a)| {
+            let p = p.clamp(eps, 1.0 - eps);
+            if a == positive {
+                -p.ln()
+            } else {
+                -(1.0 - p).ln()
+            }
+        })
+        .sum();
+    total / probs.len() as f64
+}
+
+/// mean squared error.
+pub fn mse(pred: &[f64], actual: &[f64]) -> f64 {
+    check_len(pred.len(), actual.len());
+    if pred.is_empty() {
+        return 0.0;
+    }
+    let total: f64 = pred
+        .iter()
+        .zip(actual)
+        .map(|(p, a)| (p - a) * (p - a))
+        .sum();
+    total / pred.len() as f64
+}
+
+/// mean absolute error.
+pub fn mae(pred: &[f64], actual: &[f64]) -> f64 {
+    check_len(pred.len(), actual.len());
+    if pred.is_empty() {
+        return 0.0;
+    }
+    let total: f64 = pred.iter().zip(actual).map(|(p, a)| (p - a).abs()).sum();
+    total / pred.len() as f64
+}
+
+/// coefficient of determination R² = 1 - (residual sum of squares / total sum of squares).
+/// 1 is a perfect fit and 0 is as good as always predicting the mean.
+/// When all the actual values are equal it returns 1 for a perfect fit else 0.
+pub fn r2(pred: &[f64], actual: &[f64]) -> f64 {
+    check_len(pred.len(), actual.len());
+    if actual.is_empty() {
+        return 0.0;
+    }
+    let mean = actual.iter().sum::<f64>() / actual.len() as f64;
+    let ss_res: f64 = pred
+        .iter()
+        .zip(actual)
+        .map(|(p, a)| (a - p) * (a - p))
+        .sum();
+    let ss_tot: f64 = actual.iter().map(|a| (a - mean) * (a - mean)).sum();
+    if ss_tot == 0.0 {
+        return if ss_res == 0.0 { 1.0 } else { 0.0 };
+    }
+    1.0 - ss_res / ss_tot
+}
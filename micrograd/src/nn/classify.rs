@@ -0,0 +1,72 @@
+use crate::value::{data_type::Float, MVal};
+
+/*
+How to use the MLP for multi class classification?
+    - Create a MLP with one output(logit) per class. The last layer should be Linear, so that
+      the logits are not limited to the range of the activation function.
+            `let model = MLP::from_layers(vec![
+                 Layer::new(2, 16),
+                 Layer::new_custom(16, 3, ActivationType::Linear),
+             ]);`
+
+    - The classes are integer labels from 0 to n_classes - 1. The loss of a sample is
+      the cross entropy of the logits and its class.
+            `loss = loss + cross_entropy(&model.forward(v), class);`
+
+    - `softmax` converts the logits to the probability of each class. The predicted class is
+      the one with the highest logit(or probability).
+            `let class = model.predict_class(&[x, y]);`
+*/
+
+/// probability of each class for the logits. exp(logit_i) / sum(exp(logit_j))
+/// The maximum logit is subtracted before the exp to avoid overflow, which doesn't change the result.
+pub fn softmax<F: Float>(logits: &[MVal<F>]) -> Vec<MVal<F>> {
+    let max = MVal::constant(max_value(logits));
+    let exps: Vec<MVal<F>> = logits
+        .iter()
+        .map(|l| (l.clone() - max.clone()).exp())
+        .collect();
+    let sum = sum(&exps);
+    exps.into_iter().map(|e| e / sum.clone()).collect()
+}
+
+/// negative log probability of the class. It is computed from the logits directly
+/// as log(sum(exp(logit_j))) - logit_class, which is more stable than taking log of the softmax.
+/// Panics if the class is not less than the number of logits.
+pub fn cross_entropy<F: Float>(logits: &[MVal<F>], class: usize) -> MVal<F> {
+    assert!(
+        class < logits.len(),
+        "expecting class {class} to be less than the number of logits {}",
+        logits.len()
+    );
+    let max = MVal::constant(max_value(logits));
+    let exps: Vec<MVal<F>> = logits
+        .iter()
+        .map(|l| (l.clone() - max.clone()).exp())
+        .collect();
+    sum(&exps).log() + max - logits[class].clone()
+}
+
+/// index of the maximum value. The first index is returned for the ties and 0 for the empty list.
+pub fn argmax<F: Float>(values: &[F]) -> usize {
+    let mut best = 0;
+    for (i, v) in values.iter().enumerate() {
+        if *v > values[best] {
+            best = i;
+        }
+    }
+    best
+}
+
+fn max_value<F: Float>(values: &[MVal<F>]) -> F {
+    let data: Vec<F> = values.iter().map(|v| v.get()).collect();
+    data.get(argmax(&data)).copied().unwrap_or_else(F::zero)
+}
+
+fn sum<F: Float>(values: &[MVal<F>]) -> MVal<F> {
+    values
+        .iter()
+        .cloned()
+        .reduce(|acc, v| acc + v)
+        .unwrap_or_else(|| MVal::constant(F::zero()))
+}
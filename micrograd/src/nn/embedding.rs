@@ -0,0 +1,113 @@
+use crate::{
+    error::{Error, Result},
+    value::{
+        data_type::{DataType, Float},
+        MVal,
+    },
+};
+use rand::Rng;
+
+/*
+What is Embedding?
+    - Embedding is a table with one learnable vector(row) of MVal for each token id. The forward
+      pass just looks up the rows of the given tokens, which is the same as multiplying the one-hot
+      encoding of the token with the weights of a Layer but without building the graph of the
+      multiplications with 0.
+
+    - Only the rows looked up in the forward pass are part of the graph, so backward accumulates
+      the gradients only into those rows. The rows that are not used in a step are not reached by
+      `loss.zero_grad()`, so call `zero_grad` of the Embedding before backward, to not update them
+      with the gradients of an earlier step.
+
+How to Use it?
+    - The embeddings of a context of tokens are concatenated, so they can be passed to a Layer
+      whose Neurons have `context length * dim` inputs.
+            `let emb = Embedding::new(27, 2);
+             let layer = Layer::new(3 * 2, 100);
+             let hidden = layer.forward(emb.forward(&[0, 5, 13]));`
+*/
+
+#[derive(Debug)]
+pub struct Embedding<F: Float = DataType> {
+    // one row of length dim for each token.
+    rows: Vec<Vec<MVal<F>>>,
+}
+
+impl Embedding {
+    /// creates embedding for the tokens 0 to n_tokens - 1 with vectors of length dim.
+    /// the values are initialised randomly between -1 and 1 like the weights of the Neuron.
+    pub fn new(n_tokens: usize, dim: usize) -> Self {
+        Self::new_typed(n_tokens, dim)
+    }
+}
+
+impl<F: Float> Embedding<F> {
+    /// Same as new but for any Float type.
+    pub fn new_typed(n_tokens: usize, dim: usize) -> Self {
+        let mut rng = rand::thread_rng();
+        let rows = (0..n_tokens)
+            .map(|_| {
+                (0..dim)
+                    .map(|_| MVal::new_typed(F::from_f64(rng.gen_range(-1.0..=1.0))))
+                    .collect()
+            })
+            .collect();
+        Self { rows }
+    }
+
+    pub fn n_tokens(&self) -> usize {
+        self.rows.len()
+    }
+
+    /// length of the vector of each token.
+    pub fn dim(&self) -> usize {
+        self.rows.first().map_or(0, |row| row.len())
+    }
+
+    /// returns the vector of the token.
+    /// returns error if the token is not less than the number of tokens.
+    pub fn try_lookup(&self, token: usize) -> Result<Vec<MVal<F>>> {
+        self.rows.get(token).cloned().ok_or(Error::TokenOutOfRange {
+            token,
+            n_tokens: self.rows.len(),
+        })
+    }
+
+    /// Same as try_lookup but panics if the token is out of range.
+    pub fn lookup(&self, token: usize) -> Vec<MVal<F>> {
+        self.try_lookup(token).unwrap_or_else(|err| panic!("{err}"))
+    }
+
+    /// returns the vectors of the tokens concatenated in the same order.
+    /// returns error for the first token that is out of range.
+    pub fn try_forward(&self, tokens: &[usize]) -> Result<Vec<MVal<F>>> {
+        let mut out = Vec::with_capacity(tokens.len() * self.dim());
+        for &token in tokens {
+            out.append(&mut self.try_lookup(token)?);
+        }
+        Ok(out)
+    }
+
+    /// Same as try_forward but panics if a token is out of range.
+    pub fn forward(&self, tokens: &[usize]) -> Vec<MVal<F>> {
+        self.try_forward(tokens)
+            .unwrap_or_else(|err| panic!("{err}"))
+    }
+
+    /// Same as forward but returns the raw Float values without the MVal. Used for inference.
+    pub fn predict(&self, tokens: &[usize]) -> Vec<F> {
+        self.forward(tokens).iter().map(|v| v.get()).collect()
+    }
+
+    /// sets the gradient of all the rows to 0 including the rows that are not used in the last step.
+    pub fn zero_grad(&self) {
+        for v in self.rows.iter().flatten() {
+            v.set_grad(MVal::default());
+        }
+    }
+
+    /// returns all the rows in the order of the token ids.
+    pub fn parameters(&self) -> Vec<MVal<F>> {
+        self.rows.iter().flatten().cloned().collect()
+    }
+}
@@ -1,6 +1,15 @@
-use crate::value::{data_type::IntoValue, MVal};
+use crate::{
+    error::{Error, Result},
+    value::{
+        data_type::{DataType, Float, IntoValue},
+        MVal,
+    },
+};
 use rand::Rng;
 
+pub mod classify;
+pub mod embedding;
+
 #[derive(Debug, Clone)]
 pub enum ActivationType {
     Linear,
@@ -9,9 +18,9 @@ pub enum ActivationType {
 }
 
 #[derive(Debug)]
-pub struct Neuron {
-    weights: Vec<MVal>,
-    b: MVal,
+pub struct Neuron<F: Float = DataType> {
+    weights: Vec<MVal<F>>,
+    b: MVal<F>,
     activation_type: ActivationType,
 }
 
@@ -21,6 +30,8 @@ What is Neuron?
       one bias Value.
 
     - the lenght of a input instance should be equal to the length of the list of weights of a Neuron.
+      `try_forward` returns Error::InputLength otherwise and `forward` panics.
+      `forward_lenient` takes the missing inputs as 0 and ignores extra inputs.
 
     - A Neuron will adjust each of its weights to port itself to compute the expected output.
       Weights of Neuron are adjusted based on the `grad` property of each weight of the Neuron.
@@ -69,7 +80,7 @@ How to Use it?
     - The above loss calculation is just a simple example. But the actual loss can be computed using any
       logic based on the use case. Now for the loss of type MVal you can calculate gradient for all its depedencies using
             `loss.zero_grad();
-             loss.backward();`
+             loss.backward()?;`
 
     - Since the computation of loss depends on all the weights of the neural MLP either directly or indirectly,
       calling `loss.backward()` will compute the gradient each of these weights in the MLP.
@@ -80,6 +91,19 @@ How to Use it?
     - Based on these gradient we can update the `data` field of the weights. Then go to the first step
     and continue this process with next input instance.
 
+    - When we only need the prediction(no training), use the predict method instead. It computes
+      the outputs with raw f64 values without building the MVal graph.
+            `let pre = model.predict(&[x, y]);`
+
+    - All of them are generic over the Float type of their parameters with f64 as the default.
+      Use the `new_typed` constructors to create a model with f32 parameters.
+            `let model = MLP::<f32>::new_typed(2, vec![16, 16, 1]);`
+
+    - To fine-tune a trained model, freeze the parameters that should not change. backward
+      doesn't compute the gradients of the frozen parameters and skips the Layers before them
+      if all of them are frozen.
+            `model.freeze_layer(0);`
+
     - Remember that there are diffent ways of choosing the training data, computing the loss and
     updating the weights. These decisions are made based on the specific use case.
 */
@@ -88,36 +112,73 @@ impl Neuron {
     /// Creates a neuron with `neuron_inp` number of weights.
     /// Here default activation type will be Tanh
     pub fn new(neuron_inp: usize) -> Self {
+        Self::new_typed(neuron_inp)
+    }
+
+    /// Same as new but with custom activation functions.
+    pub fn new_custom(neuron_inp: usize, activation_type: ActivationType) -> Self {
+        Self::new_custom_typed(neuron_inp, activation_type)
+    }
+}
+
+impl<F: Float> Neuron<F> {
+    /// Same as new but for any Float type.
+    pub fn new_typed(neuron_inp: usize) -> Self {
         let mut weights = vec![];
         let mut rng = rand::thread_rng();
         // Generate a random floating-point number between -1 and 1
         for _ in 0..neuron_inp {
-            weights.push(MVal::new(rng.gen_range(-1.0..=1.0)));
+            weights.push(MVal::new_typed(F::from_f64(rng.gen_range(-1.0..=1.0))));
         }
         Neuron {
             weights,
-            b: MVal::new(rng.gen_range(-1.0..=1.0)),
+            b: MVal::new_typed(F::from_f64(rng.gen_range(-1.0..=1.0))),
             activation_type: ActivationType::Tanh,
         }
     }
 
-    /// Same as new but with custom activation functions.
-    pub fn new_custom(neuron_inp: usize, activation_type: ActivationType) -> Self {
-        let mut ner = Self::new(neuron_inp);
+    /// Same as new_custom but for any Float type.
+    pub fn new_custom_typed(neuron_inp: usize, activation_type: ActivationType) -> Self {
+        let mut ner = Self::new_typed(neuron_inp);
         ner.activation_type = activation_type;
         ner
     }
 
+    /// returns error if the length of input instance is not equal to the number of weights.
+    fn check_input(&self, len: usize) -> Result<()> {
+        if len != self.weights.len() {
+            return Err(Error::InputLength {
+                expected: self.weights.len(),
+                actual: len,
+            });
+        }
+        Ok(())
+    }
+
     /// Multiplies each value of input instance with each weight of the neuron
     /// add the bias to the final sum and compute the activation value for it.
     /// ouput = activation((w_1 * x_1 + w_2 * x_2 + ... + w_k * x_k) + b)
-    pub fn forward(&self, input: Vec<MVal>) -> MVal {
+    /// returns error if the length of input is not equal to the number of weights.
+    pub fn try_forward(&self, input: Vec<MVal<F>>) -> Result<MVal<F>> {
+        self.check_input(input.len())?;
+        Ok(self.forward_lenient(input))
+    }
+
+    /// Same as try_forward but panics if the length of input is not equal to the number of weights.
+    pub fn forward(&self, input: Vec<MVal<F>>) -> MVal<F> {
+        self.try_forward(input)
+            .unwrap_or_else(|err| panic!("{err}"))
+    }
+
+    /// Same as forward but never fails. Missing inputs are taken as 0 and extra inputs are ignored.
+    pub fn forward_lenient(&self, input: Vec<MVal<F>>) -> MVal<F> {
         let mut sum = self.b.clone();
-        let mut i = 0;
-        for w in &self.weights {
-            let inp = input.get(i).unwrap_or(&MVal::new(0.0)).clone();
+        for (i, w) in self.weights.iter().enumerate() {
+            let inp = input
+                .get(i)
+                .cloned()
+                .unwrap_or_else(|| MVal::constant(F::zero()));
             sum = sum + (w.clone() * inp);
-            i += 1;
         }
 
         match self.activation_type {
@@ -127,66 +188,175 @@ impl Neuron {
         }
     }
 
+    /// Same as forward but computes with raw Float values without building the MVal graph.
+    /// Used for inference where gradients are not needed.
+    pub fn predict(&self, input: &[F]) -> F {
+        self.check_input(input.len())
+            .unwrap_or_else(|err| panic!("{err}"));
+        let mut sum = self.b.get();
+        for (w, x) in self.weights.iter().zip(input) {
+            sum += w.get() * *x;
+        }
+
+        match self.activation_type {
+            ActivationType::Tanh => sum.tanh(),
+            ActivationType::ReLU => {
+                if sum > F::zero() {
+                    sum
+                } else {
+                    F::zero()
+                }
+            }
+            ActivationType::Linear => sum,
+        }
+    }
+
     /// returns list of weights and bias. these are the parameters of a Neuron.
-    pub fn parameters(&self) -> Vec<MVal> {
+    pub fn parameters(&self) -> Vec<MVal<F>> {
         let mut params = self.weights.clone();
         params.push(self.b.clone());
         params
     }
+
+    /// stops computing the gradients of the weights and bias, so the optimizer won't update them.
+    pub fn freeze(&self) {
+        self.parameters()
+            .iter()
+            .for_each(|p| p.set_requires_grad(false));
+    }
+
+    pub fn unfreeze(&self) {
+        self.parameters()
+            .iter()
+            .for_each(|p| p.set_requires_grad(true));
+    }
+
+    /// true if none of the parameters requires the gradient.
+    pub fn is_frozen(&self) -> bool {
+        self.parameters().iter().all(|p| !p.requires_grad())
+    }
 }
 
 #[derive(Debug)]
-pub struct Layer {
-    neurons: Vec<Neuron>,
+pub struct Layer<F: Float = DataType> {
+    neurons: Vec<Neuron<F>>,
 }
 
 impl Layer {
     /// Creates a Layer with `layer_out` number of neurons with each neuron having
     /// `neuron_inp` number of inputs their corresponding weights.
     pub fn new(neuron_inp: usize, layer_out: usize) -> Self {
+        Self::new_typed(neuron_inp, layer_out)
+    }
+
+    /// Same as new but with custom activation function for Neurons
+    pub fn new_custom(
+        neuron_inp: usize,
+        layer_out: usize,
+        activation_type: ActivationType,
+    ) -> Self {
+        Self::new_custom_typed(neuron_inp, layer_out, activation_type)
+    }
+}
+
+impl<F: Float> Layer<F> {
+    /// Same as new but for any Float type.
+    pub fn new_typed(neuron_inp: usize, layer_out: usize) -> Self {
         let mut neurons = vec![];
         for _ in 0..layer_out {
-            neurons.push(Neuron::new(neuron_inp));
+            neurons.push(Neuron::new_typed(neuron_inp));
         }
         Self { neurons }
     }
 
-    /// Same as new but with custom activation function for Neurons
-    pub fn new_custom(
+    /// Same as new_custom but for any Float type.
+    pub fn new_custom_typed(
         neuron_inp: usize,
         layer_out: usize,
         activation_type: ActivationType,
     ) -> Self {
         let mut neurons = vec![];
         for _ in 0..layer_out {
-            neurons.push(Neuron::new_custom(neuron_inp, activation_type.clone()));
+            neurons.push(Neuron::new_custom_typed(
+                neuron_inp,
+                activation_type.clone(),
+            ));
         }
         Self { neurons }
     }
 
     /// passes same input instance to each Neuron of the current Layer
-    /// and returns a list of predicted outputs
-    pub fn forward(&self, input: Vec<MVal>) -> Vec<MVal> {
+    /// and returns a list of predicted outputs.
+    /// returns error if the length of input is not equal to the number of inputs of the Neurons.
+    pub fn try_forward(&self, input: Vec<MVal<F>>) -> Result<Vec<MVal<F>>> {
+        let mut outs = vec![];
+        for n in &self.neurons {
+            outs.push(n.try_forward(input.clone())?);
+        }
+        Ok(outs)
+    }
+
+    /// Same as try_forward but panics on the input length mismatch.
+    pub fn forward(&self, input: Vec<MVal<F>>) -> Vec<MVal<F>> {
+        self.try_forward(input)
+            .unwrap_or_else(|err| panic!("{err}"))
+    }
+
+    /// Same as forward but each Neuron takes missing inputs as 0 and ignores extra inputs.
+    pub fn forward_lenient(&self, input: Vec<MVal<F>>) -> Vec<MVal<F>> {
         let mut outs = vec![];
         for n in &self.neurons {
-            outs.push(n.forward(input.clone()));
+            outs.push(n.forward_lenient(input.clone()));
         }
         outs
     }
 
+    /// passes each input instance of the batch to the Layer and returns
+    /// the list of outputs for each instance in the same order.
+    pub fn try_forward_batch(&self, batch: &[Vec<MVal<F>>]) -> Result<Vec<Vec<MVal<F>>>> {
+        batch
+            .iter()
+            .map(|inp| self.try_forward(inp.clone()))
+            .collect()
+    }
+
+    /// Same as try_forward_batch but panics on the input length mismatch.
+    pub fn forward_batch(&self, batch: &[Vec<MVal<F>>]) -> Vec<Vec<MVal<F>>> {
+        self.try_forward_batch(batch)
+            .unwrap_or_else(|err| panic!("{err}"))
+    }
+
+    /// Same as forward but without building the MVal graph.
+    pub fn predict(&self, input: &[F]) -> Vec<F> {
+        self.neurons.iter().map(|n| n.predict(input)).collect()
+    }
+
     // returns a list of parameters of all Neurons in the current Layer.
-    pub fn parameters(&self) -> Vec<MVal> {
+    pub fn parameters(&self) -> Vec<MVal<F>> {
         let mut params = vec![];
         for n in &self.neurons {
             params.append(&mut n.parameters());
         }
         params
     }
+
+    /// freezes all the Neurons of the Layer.
+    pub fn freeze(&self) {
+        self.neurons.iter().for_each(|n| n.freeze());
+    }
+
+    pub fn unfreeze(&self) {
+        self.neurons.iter().for_each(|n| n.unfreeze());
+    }
+
+    pub fn is_frozen(&self) -> bool {
+        self.neurons.iter().all(|n| n.is_frozen())
+    }
 }
 
 #[derive(Debug)]
-pub struct MLP {
-    layers: Vec<Layer>,
+pub struct MLP<F: Float = DataType> {
+    layers: Vec<Layer<F>>,
 }
 
 impl MLP {
@@ -196,18 +366,41 @@ impl MLP {
     /// Remember that number of Neurons on the i'th layer will be the
     /// length of input for each Neuron in (i+1)'th layer
     pub fn new(neuron_inp: usize, layer_outs: Vec<usize>) -> Self {
+        Self::new_typed(neuron_inp, layer_outs)
+    }
+
+    /// new MLP with custom activation like ReLU, Linear for all Neurons.
+    pub fn new_custom(
+        neuron_inp: usize,
+        layer_outs: Vec<usize>,
+        activation_type: ActivationType,
+    ) -> Self {
+        Self::new_custom_typed(neuron_inp, layer_outs, activation_type)
+    }
+}
+
+impl<F: Float> MLP<F> {
+    /// Same as new but for any Float type. For example MLP::<f32>::new_typed(2, vec![16, 16, 1])
+    pub fn new_typed(neuron_inp: usize, layer_outs: Vec<usize>) -> Self {
         let mut lays = vec![];
         let mut no_of_input = neuron_inp;
         for layer_out in layer_outs {
-            lays.push(Layer::new(no_of_input, layer_out));
+            lays.push(Layer::new_typed(no_of_input, layer_out));
             // for each inner layer no of input will be equal to the no of output of previous layer.
             no_of_input = layer_out;
         }
         Self { layers: lays }
     }
 
-    /// new MLP with custom activation like ReLU, Linear for all Neurons.
-    pub fn new_custom(
+    /// creates MLP from the list of layers, so each layer can have its own activation.
+    /// Remember that number of Neurons on the i'th layer should be the
+    /// number of inputs of the Neurons in the (i+1)'th layer.
+    pub fn from_layers(layers: Vec<Layer<F>>) -> Self {
+        Self { layers }
+    }
+
+    /// Same as new_custom but for any Float type.
+    pub fn new_custom_typed(
         neuron_inp: usize,
         layer_outs: Vec<usize>,
         activation_type: ActivationType,
@@ -215,7 +408,7 @@ impl MLP {
         let mut lays = vec![];
         let mut no_of_input = neuron_inp;
         for layer_out in layer_outs {
-            lays.push(Layer::new_custom(
+            lays.push(Layer::new_custom_typed(
                 no_of_input,
                 layer_out,
                 activation_type.clone(),
@@ -227,24 +420,107 @@ impl MLP {
     }
 
     /// inp - length should be equal to no of inputs of each neuron in the first layer.
-    /// The `forward` method will pass same input instance to each Neuron of the first layer.
+    /// The `try_forward` method will pass same input instance to each Neuron of the first layer.
     /// Then ouputs predicted by i'th Layer will be passed as input for
     /// each Neuron in the (i+1)'th Layer.
-    pub fn forward<T: IntoValue>(&self, inp: Vec<T>) -> Vec<MVal> {
-        let mut input: Vec<_> = inp.into_iter().map(|v| MVal::new(v)).collect();
+    /// returns error if the length of inp is not equal to the no of inputs of the first layer.
+    pub fn try_forward<T: IntoValue<F>>(&self, inp: Vec<T>) -> Result<Vec<MVal<F>>> {
+        // the inputs are constants, the gradients are only needed for the parameters.
+        let mut input: Vec<_> = inp
+            .into_iter()
+            .map(|v| MVal::constant(v.into_value()))
+            .collect();
         for n in &self.layers {
             // for the next layer the input will be current layers output.
-            input = n.forward(input.clone());
+            input = n.try_forward(input.clone())?;
+        }
+        Ok(input)
+    }
+
+    /// Same as try_forward but panics on the input length mismatch.
+    pub fn forward<T: IntoValue<F>>(&self, inp: Vec<T>) -> Vec<MVal<F>> {
+        self.try_forward(inp).unwrap_or_else(|err| panic!("{err}"))
+    }
+
+    /// Same as forward but missing inputs are taken as 0 and extra inputs are ignored.
+    pub fn forward_lenient<T: IntoValue<F>>(&self, inp: Vec<T>) -> Vec<MVal<F>> {
+        // the inputs are constants, the gradients are only needed for the parameters.
+        let mut input: Vec<_> = inp
+            .into_iter()
+            .map(|v| MVal::constant(v.into_value()))
+            .collect();
+        for n in &self.layers {
+            // for the next layer the input will be current layers output.
+            input = n.forward_lenient(input.clone());
         }
         input
     }
 
+    /// batch - list of input instances. Each instance is passed through the whole MLP
+    /// and the list of outputs for each instance is returned in the same order.
+    /// returns the error of the first instance whose length is not matching.
+    pub fn try_forward_batch<T: IntoValue<F> + Clone>(
+        &self,
+        batch: &[Vec<T>],
+    ) -> Result<Vec<Vec<MVal<F>>>> {
+        batch
+            .iter()
+            .map(|inp| self.try_forward(inp.clone()))
+            .collect()
+    }
+
+    /// Same as try_forward_batch but panics on the input length mismatch.
+    pub fn forward_batch<T: IntoValue<F> + Clone>(&self, batch: &[Vec<T>]) -> Vec<Vec<MVal<F>>> {
+        self.try_forward_batch(batch)
+            .unwrap_or_else(|err| panic!("{err}"))
+    }
+
+    /// Same as forward but computes with raw Float values without building the MVal graph.
+    /// So it is much faster and allocation light. Use it when only the prediction is needed.
+    pub fn predict(&self, inp: &[F]) -> Vec<F> {
+        let mut input = inp.to_vec();
+        for n in &self.layers {
+            input = n.predict(&input);
+        }
+        input
+    }
+
+    /// index of the output with the highest value. Used for multi class classification
+    /// where each output of the last layer is the logit of a class.
+    pub fn predict_class(&self, inp: &[F]) -> usize {
+        classify::argmax(&self.predict(inp))
+    }
+
     /// returns list of all the weights and biases of all Neurons in the MLP.
-    pub fn parameters(&mut self) -> Vec<MVal> {
+    pub fn parameters(&mut self) -> Vec<MVal<F>> {
         let mut params = vec![];
         for n in &self.layers {
             params.append(&mut n.parameters());
         }
         params
     }
+
+    /// freezes all the Layers of the MLP.
+    pub fn freeze(&self) {
+        self.layers.iter().for_each(|l| l.freeze());
+    }
+
+    pub fn unfreeze(&self) {
+        self.layers.iter().for_each(|l| l.unfreeze());
+    }
+
+    /// freezes the Layer at the index(0 is the first layer). Used for fine-tuning only
+    /// the last layers of a trained MLP. Panics if the index is out of range.
+    pub fn freeze_layer(&self, index: usize) {
+        self.layers[index].freeze();
+    }
+
+    /// Panics if the index is out of range.
+    pub fn unfreeze_layer(&self, index: usize) {
+        self.layers[index].unfreeze();
+    }
+
+    pub fn is_frozen(&self) -> bool {
+        self.layers.iter().all(|l| l.is_frozen())
+    }
 }
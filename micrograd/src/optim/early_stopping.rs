@@ -0,0 +1,71 @@
+use crate::value::{data_type::Float, MVal};
+
+/*
+How does the early stopping work?
+    - A part of the samples(validation set) is kept aside and never used for training. The loss on
+      the validation set is evaluated every few steps and passed to `update`.
+
+    - When the validation loss improves by more than `min_delta`, the weights are saved as the best
+      weights. When it doesn't improve for `patience` evaluations in a row, the model has started to
+      overfit the training set(or stopped learning), so `update` returns true to stop the training.
+
+    - After the training call `restore_best` to set the parameters back to the best weights seen.
+            `if early_stopping.update(step, val_loss, &params) { break; }
+             ...
+             early_stopping.restore_best(&params);`
+*/
+
+#[derive(Debug, Clone)]
+pub struct EarlyStopping<F: Float> {
+    patience: usize,
+    min_delta: f64,
+    best_loss: f64,
+    best_step: usize,
+    best_weights: Vec<F>,
+    // number of evaluations since the last improvement.
+    bad_evals: usize,
+}
+
+impl<F: Float> EarlyStopping<F> {
+    /// patience - number of evaluations without improvement to wait before stopping.
+    /// min_delta - minimum decrease of the validation loss that counts as an improvement.
+    pub fn new(patience: usize, min_delta: f64) -> Self {
+        Self {
+            patience,
+            min_delta,
+            best_loss: f64::INFINITY,
+            best_step: 0,
+            best_weights: vec![],
+            bad_evals: 0,
+        }
+    }
+
+    /// records the validation loss of the step and saves the params if it is the best so far.
+    /// returns true when the training should be stopped.
+    pub fn update(&mut self, step: usize, val_loss: f64, params: &[MVal<F>]) -> bool {
+        if val_loss < self.best_loss - self.min_delta {
+            self.best_loss = val_loss;
+            self.best_step = step;
+            self.best_weights = params.iter().map(|p| p.get()).collect();
+            self.bad_evals = 0;
+        } else {
+            self.bad_evals += 1;
+        }
+        self.bad_evals >= self.patience
+    }
+
+    /// sets the params to the best weights seen. Does nothing if update was never called.
+    pub fn restore_best(&self, params: &[MVal<F>]) {
+        for (p, &w) in params.iter().zip(&self.best_weights) {
+            p.set(MVal::new_typed(w));
+        }
+    }
+
+    pub fn best_loss(&self) -> f64 {
+        self.best_loss
+    }
+
+    pub fn best_step(&self) -> usize {
+        self.best_step
+    }
+}
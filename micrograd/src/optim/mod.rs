@@ -0,0 +1,111 @@
+use crate::value::{data_type::Float, MVal};
+
+mod early_stopping;
+pub use early_stopping::EarlyStopping;
+
+/*
+What is an optimizer?
+    - After the backward pass the optimizer updates the `data` of each parameter using its `grad`.
+      The simplest update is the gradient descent `p = p - learning_rate * grad`.
+
+    - Sgd adds momentum to it. It keeps a velocity for each parameter which is the running sum of
+      its past gradients decayed by the momentum. So the parameters keep moving in the direction
+      of the consistent gradients and the noise of the individual steps is smoothed out.
+            `velocity = momentum * velocity + grad
+             p = p - learning_rate * velocity`
+
+    - LinearDecay decreases the learning rate linearly from `start` to `end` over `total_steps`
+      and keeps it at `end` after that.
+
+How to Use it?
+    - Call `step` of the optimizer with the learning rate of the scheduler after each backward
+      pass and then move the scheduler to the next step.
+            `optimizer.step(&model.parameters(), scheduler.learning_rate());
+             scheduler.step();`
+*/
+
+#[derive(Debug, Clone)]
+pub struct Sgd {
+    momentum: f64,
+    // velocity of each parameter. It is created on the first step.
+    velocity: Vec<f64>,
+}
+
+impl Sgd {
+    /// momentum - 0 means plain gradient descent without momentum.
+    pub fn new(momentum: f64) -> Self {
+        Self {
+            momentum,
+            velocity: vec![],
+        }
+    }
+
+    /// updates the data of each parameter using its grad.
+    /// params should be given in the same order for every step.
+    /// Frozen parameters(not requiring the gradient) are not updated and keep their velocity.
+    pub fn step<F: Float>(&mut self, params: &[MVal<F>], learning_rate: f64) {
+        if self.velocity.len() != params.len() {
+            self.velocity = vec![0.0; params.len()];
+        }
+        for (p, v) in params.iter().zip(self.velocity.iter_mut()) {
+            if !p.requires_grad() {
+                continue;
+            }
+            *v = self.momentum * *v + p.grad().to_f64();
+            let data = p.get().to_f64() - learning_rate * *v;
+            p.set(MVal::new_typed(F::from_f64(data)));
+        }
+    }
+
+    /// velocity of each parameter. This is the state that has to be saved to resume the training.
+    pub fn velocity(&self) -> &[f64] {
+        &self.velocity
+    }
+
+    pub fn set_velocity(&mut self, velocity: Vec<f64>) {
+        self.velocity = velocity;
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct LinearDecay {
+    start: f64,
+    end: f64,
+    total_steps: usize,
+    // number of steps taken so far.
+    current: usize,
+}
+
+impl LinearDecay {
+    pub fn new(start: f64, end: f64, total_steps: usize) -> Self {
+        Self {
+            start,
+            end,
+            total_steps,
+            current: 0,
+        }
+    }
+
+    /// learning rate of the current step.
+    pub fn learning_rate(&self) -> f64 {
+        if self.total_steps == 0 || self.current >= self.total_steps {
+            return self.end;
+        }
+        let progress = self.current as f64 / self.total_steps as f64;
+        self.start + (self.end - self.start) * progress
+    }
+
+    /// moves to the next step.
+    pub fn step(&mut self) {
+        self.current += 1;
+    }
+
+    /// number of steps taken so far. This is the state that has to be saved to resume the training.
+    pub fn current(&self) -> usize {
+        self.current
+    }
+
+    pub fn set_current(&mut self, current: usize) {
+        self.current = current;
+    }
+}
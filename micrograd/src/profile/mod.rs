@@ -0,0 +1,70 @@
+use std::{
+    fmt::Display,
+    time::{Duration, Instant},
+};
+
+pub use crate::value::stats::GraphStats;
+use crate::{
+    error::Result,
+    value::{data_type::Float, MVal},
+};
+
+/*
+What is profiled?
+    - The time taken to build the graph of the loss(forward), the time taken by zero_grad and
+      backward on it and the statistics of the graph. It shows where the time of a training step
+      goes and how it grows with the size of the model and the batch.
+
+How to Use it?
+    - Pass a closure that computes the loss. The closure is called once and backward is
+      called on the returned loss, so the gradients of the parameters are updated as usual.
+            `let profile = profile(|| {
+                 let mut loss = MVal::new(0.0);
+                 for (inp, out) in batch.iter().zip(&outs) {
+                     loss = loss + (1.0 - model.forward(inp.clone())[0].clone() * *out);
+                 }
+                 loss
+             })?;
+             println!("{profile}");`
+*/
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Profile {
+    pub forward: Duration,
+    /// time of zero_grad and backward.
+    pub backward: Duration,
+    pub stats: GraphStats,
+}
+
+/// times the forward closure and the backward of the Value returned by it.
+/// returns the error of backward.
+pub fn profile<F: Float, Func: FnOnce() -> MVal<F>>(forward: Func) -> Result<Profile> {
+    let start = Instant::now();
+    let mut out = forward();
+    let forward = start.elapsed();
+    // backward frees the graph, so the stats are collected before it.
+    let stats = out.graph_stats();
+
+    let start = Instant::now();
+    out.zero_grad();
+    out.backward()?;
+    let backward = start.elapsed();
+
+    Ok(Profile {
+        forward,
+        backward,
+        stats,
+    })
+}
+
+impl Display for Profile {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(
+            f,
+            "forward:{:.2}ms, backward:{:.2}ms",
+            self.forward.as_secs_f64() * 1000.0,
+            self.backward.as_secs_f64() * 1000.0
+        )?;
+        write!(f, "{}", self.stats)
+    }
+}
@@ -0,0 +1,232 @@
+use std::{
+    collections::{HashMap, HashSet},
+    hash::{Hash, Hasher},
+    sync::{Arc, RwLock, RwLockReadGuard, RwLockWriteGuard},
+};
+
+use crate::value::{
+    data_type::{DataType, IntoValue},
+    Operator,
+};
+
+pub mod nn;
+pub mod ops;
+pub mod parallel;
+
+/*
+What is AVal?
+    - AVal is the thread safe version of MVal. It is wrapped with Arc and RwLock instead of
+      Rc and RefCell, so it is Send + Sync and the same AVal can be used from multiple threads.
+
+    - It supports the same operations(+, -, *, /, tanh, relu, exp, pow) and the same
+      backward, zero_grad, grad, get and set methods as MVal.
+
+    - Unlike MVal the intermediate nodes don't store a formatted label. Only the leaves created
+      with `new_lab` have a label, since labels are just for debugging.
+
+    - Locks are held only while reading or updating a single node, so using the same AVal
+      on both sides of an operation like `a.clone() + a` will not deadlock.
+
+Why do we need it?
+    - Data parallel training. The parameters of the model are shared between worker threads,
+      each worker builds the graph of its own shard of samples on top of the shared parameters
+      and computes the gradients of that shard. See `parallel_backward` for the details.
+*/
+
+pub struct AValue {
+    data: DataType,
+    grad: DataType,
+    operands: Vec<AVal>,
+    operator: Operator,
+    // for debugging purpose. only the leaves will have a label.
+    label: String,
+}
+
+/// Cloning of AVal is a cheap operation since it is acutally wrapper around Arc and RwLock.
+/// If you try to use the same AVal in two places, then its gradient will be the
+/// accumulated sum of gradients of all the places it has been used.
+#[derive(Clone)]
+pub struct AVal(Arc<RwLock<AValue>>);
+
+impl AVal {
+    pub fn new<T: IntoValue>(data: T) -> Self {
+        Self::from_op(data.into_value(), vec![], Operator::None)
+    }
+    pub fn new_lab<T: IntoValue>(data: T, label: &str) -> Self {
+        let val = Self::new(data);
+        val.write().label = label.to_string();
+        val
+    }
+
+    pub(crate) fn from_op(data: DataType, operands: Vec<AVal>, operator: Operator) -> Self {
+        Self(Arc::new(RwLock::new(AValue {
+            data,
+            grad: 0.0,
+            operands,
+            operator,
+            label: String::new(),
+        })))
+    }
+
+    // A panic in other thread while holding the lock can only leave a partially
+    // accumulated grad, so we still read the value from a poisoned lock.
+    pub(crate) fn read(&self) -> RwLockReadGuard<'_, AValue> {
+        self.0.read().unwrap_or_else(|err| err.into_inner())
+    }
+    pub(crate) fn write(&self) -> RwLockWriteGuard<'_, AValue> {
+        self.0.write().unwrap_or_else(|err| err.into_inner())
+    }
+
+    pub fn grad(&self) -> DataType {
+        self.read().grad
+    }
+    /// to mutate the gradient inplace instead creating new instance.
+    pub fn set_grad(&self, val: AVal) {
+        let grad = val.grad();
+        self.write().grad = grad;
+    }
+
+    pub fn get(&self) -> DataType {
+        self.read().data
+    }
+    /// to mutate the data inplace instead creating new instance.
+    pub fn set(&self, val: AVal) {
+        let data = val.get();
+        self.write().data = data;
+    }
+
+    pub fn label(&self) -> String {
+        self.read().label.clone()
+    }
+
+    /// returns the operands of the current node with the local gradient of the node w.r.t each of them.
+    /// The same operand is returned twice for expressions like x + x, so the
+    /// caller can just accumulate the gradients.
+    fn local_grads(&self) -> Vec<(AVal, DataType)> {
+        let val = self.read();
+        // read the operand data one at a time, so that using the same AVal twice won't deadlock.
+        let data: Vec<DataType> = val.operands.iter().map(|op| op.get()).collect();
+        let grads = match (val.operator, data.as_slice()) {
+            // if y = x + z, then dy/dx = 1 and dy/dz = 1
+            (Operator::Plus, [_, _]) => vec![1.0, 1.0],
+            // if y = x - z, then dy/dx = 1 and dy/dz = -1
+            (Operator::Minus, [_, _]) => vec![1.0, -1.0],
+            // if y = x * z, then dy/dx = z and dy/dz = x
+            (Operator::Mul, [x, z]) => vec![*z, *x],
+            // y=x/z then dy/dx = 1/z and dy/dz = -x/z^2.
+            (Operator::Div, [x, z]) => vec![1.0 / z, -x / (z * z)],
+            // y = tahh(x) then dy/dx = 1 - (tanh(x))^2
+            (Operator::Tanh, [_]) => vec![1.0 - val.data * val.data],
+            // ReLU y = max(0,x). dy/dx = 1 for x > 0.
+            (Operator::ReLU, [_]) => vec![if val.data > 0.0 { 1.0 } else { 0.0 }],
+            // y = exp(x) then dy/dx = exp(x)
+            (Operator::Exp, [_]) => vec![val.data],
+            // y = x^n then dy/dx = n * x^(n-1). power is a constant.
+            (Operator::Pow, [x, n]) => vec![n * x.powf(n - 1.0), 0.0],
+            _ => vec![],
+        };
+        val.operands.iter().cloned().zip(grads).collect()
+    }
+
+    /// update the gradient values inplace.
+    pub fn backward(&self) {
+        let mut all_nodes = self.collect_operands();
+        all_nodes.reverse();
+
+        // set the gradient of the root node to 1 since gradient with itself is 1.
+        self.write().grad = 1.0;
+        for node in all_nodes {
+            let out_grad = node.grad();
+            for (op, local) in node.local_grads() {
+                op.write().grad += out_grad * local;
+            }
+        }
+    }
+
+    /// Same as backward but the gradients are returned in a map instead of
+    /// accumulating them in the `grad` field of the nodes. So nodes shared with other threads
+    /// are only read and never mutated.
+    // AVal is hashed by the address of the Arc, so mutating the inner value never changes its hash.
+    #[allow(clippy::mutable_key_type)]
+    pub fn gradients(&self) -> HashMap<AVal, DataType> {
+        let mut all_nodes = self.collect_operands();
+        all_nodes.reverse();
+
+        let mut grads = HashMap::new();
+        grads.insert(self.clone(), 1.0);
+        for node in all_nodes {
+            let out_grad = grads.get(&node).copied().unwrap_or(0.0);
+            for (op, local) in node.local_grads() {
+                *grads.entry(op).or_insert(0.0) += out_grad * local;
+            }
+        }
+        grads
+    }
+
+    /// collect all the Values that a calling Value is depending on in the topological order.
+    #[allow(clippy::mutable_key_type)]
+    pub fn collect_operands(&self) -> Vec<AVal> {
+        let mut visited = HashSet::new();
+        let mut all_nodes = vec![];
+        self.collect_operands_inner(&mut visited, &mut all_nodes);
+        all_nodes
+    }
+
+    #[allow(clippy::mutable_key_type)]
+    fn collect_operands_inner(&self, visited: &mut HashSet<AVal>, all_nodes: &mut Vec<AVal>) {
+        if visited.insert(self.clone()) {
+            let operands = self.read().operands.clone();
+            for op in operands {
+                op.collect_operands_inner(visited, all_nodes);
+            }
+            all_nodes.push(self.clone());
+        }
+    }
+
+    /// set the gradient value of the calling Value and all its children to 0.
+    pub fn zero_grad(&self) {
+        for node in self.collect_operands() {
+            node.write().grad = 0.0;
+        }
+    }
+}
+
+impl Default for AVal {
+    fn default() -> Self {
+        Self::new(0)
+    }
+}
+
+/// Implement Hash based on the address of the Arc
+impl Hash for AVal {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        let ptr = Arc::as_ptr(&self.0) as *const (); // Get raw pointer address
+        ptr.hash(state);
+    }
+}
+
+/// Implement Eq and PartialEq based on the address of the Arc
+impl PartialEq for AVal {
+    fn eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.0, &other.0)
+    }
+}
+
+impl Eq for AVal {}
+
+impl std::fmt::Display for AVal {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Value({})", self.get())
+    }
+}
+
+impl std::fmt::Debug for AVal {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let val = self.read();
+        write!(
+            f,
+            "Value(data:{}, grad:{}, {:?}, {})",
+            val.data, val.grad, val.operator, val.label,
+        )
+    }
+}
@@ -0,0 +1,122 @@
+use super::AVal;
+use crate::{
+    error::{Error, Result},
+    nn::ActivationType,
+    value::data_type::IntoValue,
+};
+use rand::Rng;
+
+/*
+AMLP is the same multi layer perceptron as MLP but built on top of AVal.
+So the same model can be shared between threads for the data parallel training.
+Read the documentation of MLP for how Neurons and Layers work.
+*/
+
+#[derive(Debug)]
+struct ANeuron {
+    weights: Vec<AVal>,
+    b: AVal,
+    activation_type: ActivationType,
+}
+
+impl ANeuron {
+    fn new(neuron_inp: usize, activation_type: ActivationType) -> Self {
+        let mut rng = rand::thread_rng();
+        // Generate a random floating-point number between -1 and 1
+        let weights = (0..neuron_inp)
+            .map(|_| AVal::new(rng.gen_range(-1.0..=1.0)))
+            .collect();
+        ANeuron {
+            weights,
+            b: AVal::new(rng.gen_range(-1.0..=1.0)),
+            activation_type,
+        }
+    }
+
+    /// ouput = activation((w_1 * x_1 + w_2 * x_2 + ... + w_k * x_k) + b)
+    fn try_forward(&self, input: &[AVal]) -> Result<AVal> {
+        if input.len() != self.weights.len() {
+            return Err(Error::InputLength {
+                expected: self.weights.len(),
+                actual: input.len(),
+            });
+        }
+        let mut sum = self.b.clone();
+        for (w, x) in self.weights.iter().zip(input) {
+            sum = sum + (w.clone() * x.clone());
+        }
+
+        Ok(match self.activation_type {
+            ActivationType::Tanh => sum.tanh(),
+            ActivationType::ReLU => sum.relu(),
+            ActivationType::Linear => sum,
+        })
+    }
+
+    fn parameters(&self) -> Vec<AVal> {
+        let mut params = self.weights.clone();
+        params.push(self.b.clone());
+        params
+    }
+}
+
+#[derive(Debug)]
+pub struct AMLP {
+    layers: Vec<Vec<ANeuron>>,
+}
+
+impl AMLP {
+    /// New AMLP with Tanh activation.
+    /// neuron_inp - number of inputs for each neuron in the first layer.
+    /// layer_outs - number of outputs(or neurons) in each layer in the order of first to last layer.
+    pub fn new(neuron_inp: usize, layer_outs: Vec<usize>) -> Self {
+        Self::new_custom(neuron_inp, layer_outs, ActivationType::Tanh)
+    }
+
+    /// new AMLP with custom activation like ReLU, Linear for all Neurons.
+    pub fn new_custom(
+        neuron_inp: usize,
+        layer_outs: Vec<usize>,
+        activation_type: ActivationType,
+    ) -> Self {
+        let mut lays = vec![];
+        let mut no_of_input = neuron_inp;
+        for layer_out in layer_outs {
+            lays.push(
+                (0..layer_out)
+                    .map(|_| ANeuron::new(no_of_input, activation_type.clone()))
+                    .collect(),
+            );
+            // for the next layer the input will be current layers output.
+            no_of_input = layer_out;
+        }
+        Self { layers: lays }
+    }
+
+    /// returns error if the length of inp is not equal to the no of inputs of the first layer.
+    pub fn try_forward<T: IntoValue>(&self, inp: Vec<T>) -> Result<Vec<AVal>> {
+        let mut input: Vec<_> = inp.into_iter().map(|v| AVal::new(v)).collect();
+        for layer in &self.layers {
+            // for the next layer the input will be current layers output.
+            input = layer
+                .iter()
+                .map(|n| n.try_forward(&input))
+                .collect::<Result<_>>()?;
+        }
+        Ok(input)
+    }
+
+    /// Same as try_forward but panics on the input length mismatch.
+    pub fn forward<T: IntoValue>(&self, inp: Vec<T>) -> Vec<AVal> {
+        self.try_forward(inp).unwrap_or_else(|err| panic!("{err}"))
+    }
+
+    /// returns list of all the weights and biases of all Neurons in the AMLP.
+    pub fn parameters(&self) -> Vec<AVal> {
+        self.layers
+            .iter()
+            .flatten()
+            .flat_map(|n| n.parameters())
+            .collect()
+    }
+}
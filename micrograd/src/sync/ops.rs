@@ -0,0 +1,144 @@
+use super::AVal;
+use crate::value::{
+    data_type::{DataType, IntoValue},
+    Operator,
+};
+use std::ops::{Add, Div, Mul, Sub};
+
+// AVal + AVal
+impl Add for AVal {
+    type Output = AVal;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        let data = self.get() + rhs.get();
+        AVal::from_op(data, vec![self, rhs], Operator::Plus)
+    }
+}
+
+// lhs needs to be cloned if it needs to be reused after the operation.
+// DataType + AVal
+impl Add<AVal> for DataType {
+    type Output = AVal;
+
+    fn add(self, rhs: AVal) -> Self::Output {
+        AVal::new(self) + rhs
+    }
+}
+
+// AVal + T
+impl<T: IntoValue> Add<T> for AVal {
+    type Output = AVal;
+
+    fn add(self, rhs: T) -> Self::Output {
+        self + AVal::new(rhs)
+    }
+}
+
+// AVal - AVal
+impl Sub for AVal {
+    type Output = AVal;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        let data = self.get() - rhs.get();
+        AVal::from_op(data, vec![self, rhs], Operator::Minus)
+    }
+}
+
+// DataType - AVal
+impl Sub<AVal> for DataType {
+    type Output = AVal;
+
+    fn sub(self, rhs: AVal) -> Self::Output {
+        AVal::new(self) - rhs
+    }
+}
+
+// AVal - T
+impl<T: IntoValue> Sub<T> for AVal {
+    type Output = AVal;
+
+    fn sub(self, rhs: T) -> Self::Output {
+        self - AVal::new(rhs)
+    }
+}
+
+// AVal * AVal
+impl Mul for AVal {
+    type Output = AVal;
+
+    fn mul(self, rhs: Self) -> Self::Output {
+        let data = self.get() * rhs.get();
+        AVal::from_op(data, vec![self, rhs], Operator::Mul)
+    }
+}
+
+// DataType * AVal
+impl Mul<AVal> for DataType {
+    type Output = AVal;
+
+    fn mul(self, rhs: AVal) -> Self::Output {
+        AVal::new(self) * rhs
+    }
+}
+
+// AVal * T
+impl<T: IntoValue> Mul<T> for AVal {
+    type Output = AVal;
+
+    fn mul(self, rhs: T) -> Self::Output {
+        self * AVal::new(rhs)
+    }
+}
+
+// AVal / AVal
+impl Div for AVal {
+    type Output = AVal;
+
+    fn div(self, rhs: Self) -> Self::Output {
+        let data = self.get() / rhs.get();
+        AVal::from_op(data, vec![self, rhs], Operator::Div)
+    }
+}
+
+// DataType / AVal
+impl Div<AVal> for DataType {
+    type Output = AVal;
+
+    fn div(self, rhs: AVal) -> Self::Output {
+        AVal::new(self) / rhs
+    }
+}
+
+// AVal / T
+impl<T: IntoValue> Div<T> for AVal {
+    type Output = AVal;
+
+    fn div(self, rhs: T) -> Self::Output {
+        self / AVal::new(rhs)
+    }
+}
+
+impl AVal {
+    pub fn tanh(self) -> AVal {
+        let data = self.get().tanh();
+        AVal::from_op(data, vec![self], Operator::Tanh)
+    }
+
+    // Rectified Linear Unit y = max(0,x)
+    pub fn relu(self) -> AVal {
+        let val = self.get();
+        let data = if val < 0.0 { 0.0 } else { val };
+        AVal::from_op(data, vec![self], Operator::ReLU)
+    }
+
+    pub fn exp(self) -> AVal {
+        let data = self.get().exp();
+        AVal::from_op(data, vec![self], Operator::Exp)
+    }
+
+    pub fn pow<T: IntoValue>(self, other: T) -> AVal {
+        let powv = other.into_value();
+        let data = self.get().powf(powv);
+        AVal::from_op(data, vec![self, AVal::new(powv)], Operator::Pow)
+    }
+}
@@ -0,0 +1,69 @@
+use super::AVal;
+use crate::value::data_type::DataType;
+use std::thread;
+
+/*
+How does the data parallel training work?
+    - The training samples are split into shards. Each shard is handled by its own worker thread.
+
+    - Each worker builds the loss of its shard on top of the shared parameters and computes the
+      gradients of that loss using `AVal::gradients`. The shared parameters are only read by
+      the workers, so the workers never wait for each other while building the graph.
+
+    - Once all the workers are done, the gradients of the shards are summed in the order of the
+      shards and added to the `grad` field of each parameter. Since the order of the sum is fixed,
+      the result is the same on every run irrespective of which thread finishes first.
+
+    - Since the gradients are added to the existing `grad`, call `zero_grad` on the parameters
+      before calling `parallel_backward` like you would do before calling `backward`.
+*/
+
+/// computes the loss of each shard and its gradients w.r.t the params on its own worker thread,
+/// then adds the sum of the shard gradients to the `grad` of each param.
+/// returns the sum of the shard losses.
+/// loss_fn - builds the loss AVal of a shard using the shared params.
+#[allow(clippy::mutable_key_type)]
+pub fn parallel_backward<S, F>(params: &[AVal], shards: &[S], loss_fn: F) -> DataType
+where
+    S: Sync,
+    F: Fn(&S) -> AVal + Sync,
+{
+    let loss_fn = &loss_fn;
+    let results: Vec<(DataType, Vec<DataType>)> = thread::scope(|scope| {
+        let workers: Vec<_> = shards
+            .iter()
+            .map(|shard| {
+                scope.spawn(move || {
+                    let loss = loss_fn(shard);
+                    let grads = loss.gradients();
+                    let param_grads = params
+                        .iter()
+                        .map(|p| grads.get(p).copied().unwrap_or(0.0))
+                        .collect();
+                    (loss.get(), param_grads)
+                })
+            })
+            .collect();
+        workers
+            .into_iter()
+            .map(|w| {
+                w.join()
+                    .expect("worker thread panicked while computing gradients")
+            })
+            .collect()
+    });
+
+    // reduce the shard gradients in the order of the shards.
+    let mut total_loss = 0.0;
+    let mut total_grads = vec![0.0; params.len()];
+    for (loss, grads) in results {
+        total_loss += loss;
+        for (total, g) in total_grads.iter_mut().zip(grads) {
+            *total += g;
+        }
+    }
+    for (p, g) in params.iter().zip(total_grads) {
+        p.write().grad += g;
+    }
+    total_loss
+}
@@ -0,0 +1,354 @@
+use std::{
+    cell::RefCell,
+    ops::{Add, Div, Mul, Sub},
+};
+
+use crate::value::data_type::{DataType, IntoValue};
+
+pub mod nn;
+
+/*
+What is Tape?
+    - Tape is an alternative autograd engine to MVal. Instead of allocating a new
+      Rc<RefCell<Value>> with a formatted label for each operation, every operation is
+      recorded as a Node at the end of a contiguous Vec. A Node refers to its operands by
+      their index in the Vec, so there are no per node heap allocations or pointers.
+
+    - Var is a small copyable handle(tape reference + index) to a Node on the Tape.
+      It supports the same operations as MVal(+, -, *, /, tanh, relu, exp, pow).
+      Both operands of an operation must be recorded on the same Tape, mixing the Vars of
+      different Tapes panics.
+
+    - Since the operands are always recorded before the result, the order of the Nodes in the
+      Vec is already a topological order. So the backward pass is just a single reverse sweep
+      over the Nodes, no graph traversal or visited set is needed.
+
+How to Use it?
+    - Create the parameters of the model first and remember the length of the tape.
+            `let tape = Tape::new();
+             let w = tape.var(0.5);
+             let params_len = tape.len();`
+
+    - For each training step build the loss, call backward and update the parameters.
+      Then truncate the tape to drop the graph of the step but keep the parameters.
+            `let loss = (w * 3.0 - 1.0).pow(2);
+             tape.backward(loss);
+             w.set(w.get() - 0.1 * w.grad());
+             tape.truncate(params_len);`
+*/
+
+#[derive(Debug, Clone, Copy)]
+enum TapeOp {
+    Leaf,
+    Add(usize, usize),
+    Sub(usize, usize),
+    Mul(usize, usize),
+    Div(usize, usize),
+    Tanh(usize),
+    ReLU(usize),
+    Exp(usize),
+    Pow(usize, DataType),
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Node {
+    data: DataType,
+    grad: DataType,
+    op: TapeOp,
+}
+
+#[derive(Debug, Default)]
+pub struct Tape {
+    nodes: RefCell<Vec<Node>>,
+}
+
+/// handle to a Node recorded on the Tape. Copying a Var is free.
+#[derive(Clone, Copy)]
+pub struct Var<'t> {
+    tape: &'t Tape,
+    index: usize,
+}
+
+impl Tape {
+    pub fn new() -> Self {
+        Self {
+            nodes: RefCell::new(vec![]),
+        }
+    }
+
+    /// records a new leaf(input or parameter) on the tape.
+    pub fn var<T: IntoValue>(&self, data: T) -> Var<'_> {
+        self.push(data.into_value(), TapeOp::Leaf)
+    }
+
+    /// returns the Var of the Node at the index.
+    pub fn get_var(&self, index: usize) -> Var<'_> {
+        assert!(index < self.len(), "index {index} is not on the tape");
+        Var { tape: self, index }
+    }
+
+    fn push(&self, data: DataType, op: TapeOp) -> Var<'_> {
+        let mut nodes = self.nodes.borrow_mut();
+        nodes.push(Node {
+            data,
+            grad: 0.0,
+            op,
+        });
+        Var {
+            tape: self,
+            index: nodes.len() - 1,
+        }
+    }
+
+    /// number of Nodes recorded on the tape.
+    pub fn len(&self) -> usize {
+        self.nodes.borrow().len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// drops all the Nodes recorded after the first `len` Nodes.
+    /// Vars pointing to the dropped Nodes must not be used after this.
+    pub fn truncate(&self, len: usize) {
+        self.nodes.borrow_mut().truncate(len);
+    }
+
+    /// computes the gradient of root w.r.t every Node recorded before it.
+    /// All the gradients are reset to 0 before the sweep, so there is no need to call zero_grad.
+    /// Panics if root is recorded on an other Tape.
+    pub fn backward(&self, root: Var) {
+        assert!(
+            std::ptr::eq(self, root.tape),
+            "expecting the root to be recorded on the same tape"
+        );
+        let mut nodes = self.nodes.borrow_mut();
+        for n in nodes.iter_mut() {
+            n.grad = 0.0;
+        }
+        // gradient with itself is 1.
+        nodes[root.index].grad = 1.0;
+
+        // operands are always recorded before the result, so the reverse order is topological.
+        for i in (0..=root.index).rev() {
+            let Node { data, grad, op } = nodes[i];
+            match op {
+                TapeOp::Leaf => {}
+                // if y = x + z, then dy/dx = 1 and dy/dz = 1
+                TapeOp::Add(x, z) => {
+                    nodes[x].grad += grad;
+                    nodes[z].grad += grad;
+                }
+                // if y = x - z, then dy/dx = 1 and dy/dz = -1
+                TapeOp::Sub(x, z) => {
+                    nodes[x].grad += grad;
+                    nodes[z].grad -= grad;
+                }
+                TapeOp::Mul(x, z) => {
+                    let (xv, zv) = (nodes[x].data, nodes[z].data);
+                    nodes[x].grad += grad * zv;
+                    nodes[z].grad += grad * xv;
+                }
+                // y=x/z then dy/dx = 1/z and dy/dz = -x/z^2.
+                TapeOp::Div(x, z) => {
+                    let (xv, zv) = (nodes[x].data, nodes[z].data);
+                    nodes[x].grad += grad / zv;
+                    nodes[z].grad += -grad * xv / (zv * zv);
+                }
+                // y = tahh(x) then dy/dx = 1 - (tanh(x))^2
+                TapeOp::Tanh(x) => nodes[x].grad += grad * (1.0 - data * data),
+                // ReLU y = max(0,x). dy/dx = 1 for x > 0.
+                TapeOp::ReLU(x) => nodes[x].grad += if data > 0.0 { grad } else { 0.0 },
+                // y = exp(x) then dy/dx = exp(x)
+                TapeOp::Exp(x) => nodes[x].grad += grad * data,
+                // y = x^n then dy/dx = n * x^(n-1)
+                TapeOp::Pow(x, n) => {
+                    let xv = nodes[x].data;
+                    nodes[x].grad += grad * n * xv.powf(n - 1.0);
+                }
+            }
+        }
+    }
+}
+
+impl<'t> Var<'t> {
+    pub fn index(&self) -> usize {
+        self.index
+    }
+
+    /// the index of a Var only makes sense on its own Tape, so the operands
+    /// of an operation must be recorded on the same Tape.
+    fn check_same_tape(&self, rhs: &Var) {
+        assert!(
+            std::ptr::eq(self.tape, rhs.tape),
+            "expecting both operands to be recorded on the same tape"
+        );
+    }
+
+    pub fn get(&self) -> DataType {
+        self.tape.nodes.borrow()[self.index].data
+    }
+    /// to mutate the data inplace. Used to update the parameters.
+    pub fn set(&self, data: DataType) {
+        self.tape.nodes.borrow_mut()[self.index].data = data;
+    }
+
+    pub fn grad(&self) -> DataType {
+        self.tape.nodes.borrow()[self.index].grad
+    }
+
+    pub fn tanh(self) -> Var<'t> {
+        self.tape.push(self.get().tanh(), TapeOp::Tanh(self.index))
+    }
+
+    // Rectified Linear Unit y = max(0,x)
+    pub fn relu(self) -> Var<'t> {
+        let val = self.get();
+        let data = if val < 0.0 { 0.0 } else { val };
+        self.tape.push(data, TapeOp::ReLU(self.index))
+    }
+
+    pub fn exp(self) -> Var<'t> {
+        self.tape.push(self.get().exp(), TapeOp::Exp(self.index))
+    }
+
+    pub fn pow<T: IntoValue>(self, other: T) -> Var<'t> {
+        let powv = other.into_value();
+        self.tape
+            .push(self.get().powf(powv), TapeOp::Pow(self.index, powv))
+    }
+}
+
+impl std::fmt::Display for Var<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Value({})", self.get())
+    }
+}
+
+impl std::fmt::Debug for Var<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let node = self.tape.nodes.borrow()[self.index];
+        write!(
+            f,
+            "Value(data:{}, grad:{}, {:?})",
+            node.data, node.grad, node.op,
+        )
+    }
+}
+
+// Var + Var
+impl<'t> Add for Var<'t> {
+    type Output = Var<'t>;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        self.check_same_tape(&rhs);
+        self.tape
+            .push(self.get() + rhs.get(), TapeOp::Add(self.index, rhs.index))
+    }
+}
+
+// DataType + Var
+impl<'t> Add<Var<'t>> for DataType {
+    type Output = Var<'t>;
+
+    fn add(self, rhs: Var<'t>) -> Self::Output {
+        rhs.tape.var(self) + rhs
+    }
+}
+
+// Var + T
+impl<'t, T: IntoValue> Add<T> for Var<'t> {
+    type Output = Var<'t>;
+
+    fn add(self, rhs: T) -> Self::Output {
+        self + self.tape.var(rhs)
+    }
+}
+
+// Var - Var
+impl<'t> Sub for Var<'t> {
+    type Output = Var<'t>;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        self.check_same_tape(&rhs);
+        self.tape
+            .push(self.get() - rhs.get(), TapeOp::Sub(self.index, rhs.index))
+    }
+}
+
+// DataType - Var
+impl<'t> Sub<Var<'t>> for DataType {
+    type Output = Var<'t>;
+
+    fn sub(self, rhs: Var<'t>) -> Self::Output {
+        rhs.tape.var(self) - rhs
+    }
+}
+
+// Var - T
+impl<'t, T: IntoValue> Sub<T> for Var<'t> {
+    type Output = Var<'t>;
+
+    fn sub(self, rhs: T) -> Self::Output {
+        self - self.tape.var(rhs)
+    }
+}
+
+// Var * Var
+impl<'t> Mul for Var<'t> {
+    type Output = Var<'t>;
+
+    fn mul(self, rhs: Self) -> Self::Output {
+        self.check_same_tape(&rhs);
+        self.tape
+            .push(self.get() * rhs.get(), TapeOp::Mul(self.index, rhs.index))
+    }
+}
+
+// DataType * Var
+impl<'t> Mul<Var<'t>> for DataType {
+    type Output = Var<'t>;
+
+    fn mul(self, rhs: Var<'t>) -> Self::Output {
+        rhs.tape.var(self) * rhs
+    }
+}
+
+// Var * T
+impl<'t, T: IntoValue> Mul<T> for Var<'t> {
+    type Output = Var<'t>;
+
+    fn mul(self, rhs: T) -> Self::Output {
+        self * self.tape.var(rhs)
+    }
+}
+
+// Var / Var
+impl<'t> Div for Var<'t> {
+    type Output = Var<'t>;
+
+    fn div(self, rhs: Self) -> Self::Output {
+        self.check_same_tape(&rhs);
+        self.tape
+            .push(self.get() / rhs.get(), TapeOp::Div(self.index, rhs.index))
+    }
+}
+
+// DataType / Var
+impl<'t> Div<Var<'t>> for DataType {
+    type Output = Var<'t>;
+
+    fn div(self, rhs: Var<'t>) -> Self::Output {
+        rhs.tape.var(self) / rhs
+    }
+}
+
+// Var / T
+impl<'t, T: IntoValue> Div<T> for Var<'t> {
+    type Output = Var<'t>;
+
+    fn div(self, rhs: T) -> Self::Output {
+        self / self.tape.var(rhs)
+    }
+}
@@ -0,0 +1,108 @@
+use super::{Tape, Var};
+use crate::{
+    error::{Error, Result},
+    nn::ActivationType,
+};
+use rand::Rng;
+
+/*
+TapeMLP is the same multi layer perceptron as MLP but its parameters are recorded on a Tape.
+    - The parameters are recorded as leaves on the tape when the TapeMLP is created and the model
+      only remembers where they start. For each neuron the weights are followed by its bias.
+
+    - Create the TapeMLP before recording anything else on the tape, so that the graph of
+      each training step can be dropped with `tape.truncate(tape_len)` while keeping the parameters.
+*/
+
+#[derive(Debug)]
+pub struct TapeMLP {
+    // (no of inputs, no of neurons) of each layer.
+    layers: Vec<(usize, usize)>,
+    activation_type: ActivationType,
+    // index of the first parameter on the tape.
+    start: usize,
+    n_params: usize,
+}
+
+impl TapeMLP {
+    /// New TapeMLP with Tanh activation whose parameters are recorded on the tape.
+    /// neuron_inp - number of inputs for each neuron in the first layer.
+    /// layer_outs - number of outputs(or neurons) in each layer in the order of first to last layer.
+    pub fn new(tape: &Tape, neuron_inp: usize, layer_outs: Vec<usize>) -> Self {
+        Self::new_custom(tape, neuron_inp, layer_outs, ActivationType::Tanh)
+    }
+
+    /// new TapeMLP with custom activation like ReLU, Linear for all Neurons.
+    pub fn new_custom(
+        tape: &Tape,
+        neuron_inp: usize,
+        layer_outs: Vec<usize>,
+        activation_type: ActivationType,
+    ) -> Self {
+        let mut rng = rand::thread_rng();
+        let start = tape.len();
+        let mut layers = vec![];
+        let mut no_of_input = neuron_inp;
+        for layer_out in layer_outs {
+            // weights and bias of each neuron.
+            for _ in 0..layer_out * (no_of_input + 1) {
+                tape.var(rng.gen_range(-1.0..=1.0));
+            }
+            layers.push((no_of_input, layer_out));
+            // for the next layer the input will be current layers output.
+            no_of_input = layer_out;
+        }
+        Self {
+            layers,
+            activation_type,
+            start,
+            n_params: tape.len() - start,
+        }
+    }
+
+    /// Same as MLP::try_forward but records the operations on the tape.
+    pub fn try_forward<'t>(&self, tape: &'t Tape, inp: &[f64]) -> Result<Vec<Var<'t>>> {
+        let (first_inp, _) = self.layers.first().copied().unwrap_or((inp.len(), 0));
+        if inp.len() != first_inp {
+            return Err(Error::InputLength {
+                expected: first_inp,
+                actual: inp.len(),
+            });
+        }
+
+        let mut input: Vec<_> = inp.iter().map(|&v| tape.var(v)).collect();
+        let mut param = self.start;
+        for &(no_of_input, layer_out) in &self.layers {
+            let mut outs = Vec::with_capacity(layer_out);
+            for _ in 0..layer_out {
+                // bias is recorded just after the weights of the neuron.
+                let mut sum = tape.get_var(param + no_of_input);
+                for (i, x) in input.iter().enumerate() {
+                    sum = sum + tape.get_var(param + i) * *x;
+                }
+                param += no_of_input + 1;
+                outs.push(match self.activation_type {
+                    ActivationType::Tanh => sum.tanh(),
+                    ActivationType::ReLU => sum.relu(),
+                    ActivationType::Linear => sum,
+                });
+            }
+            // for the next layer the input will be current layers output.
+            input = outs;
+        }
+        Ok(input)
+    }
+
+    /// Same as try_forward but panics on the input length mismatch.
+    pub fn forward<'t>(&self, tape: &'t Tape, inp: &[f64]) -> Vec<Var<'t>> {
+        self.try_forward(tape, inp)
+            .unwrap_or_else(|err| panic!("{err}"))
+    }
+
+    /// returns list of all the weights and biases of the TapeMLP.
+    pub fn parameters<'t>(&self, tape: &'t Tape) -> Vec<Var<'t>> {
+        (self.start..self.start + self.n_params)
+            .map(|i| tape.get_var(i))
+            .collect()
+    }
+}
@@ -0,0 +1,87 @@
+use crate::{detect_anomaly, BackwardOptions, Error, MVal};
+
+#[test]
+fn detects_the_forward_anomaly_when_the_node_is_created() {
+    let anomaly = detect_anomaly();
+    let a = MVal::new_lab(0.0, "a");
+    let b = MVal::new_lab(1.0, "b");
+    let c = b / a.clone();
+    assert_eq!(
+        anomaly.check().unwrap_err().to_string(),
+        "forward pass produced inf at the Div node (b/a)"
+    );
+    // the first anomaly is kept even though it spreads to the Values depending on it.
+    let _ = (c * 2.0).exp() + a;
+    assert!(matches!(
+        anomaly.first(),
+        Some(Error::NonFinite {
+            stage: "forward",
+            ..
+        })
+    ));
+    assert_eq!(
+        anomaly.first().unwrap().to_string(),
+        "forward pass produced inf at the Div node (b/a)"
+    );
+}
+
+#[test]
+fn detects_nan_of_pow_and_inf_of_exp() {
+    let anomaly = detect_anomaly();
+    let x = MVal::new_lab(-1.0, "x");
+    let _ = x.pow(0.5);
+    assert_eq!(
+        anomaly.check().unwrap_err().to_string(),
+        "forward pass produced NaN at the Pow node pow(x,0.5)"
+    );
+    drop(anomaly);
+
+    let anomaly = detect_anomaly();
+    let _ = MVal::new_lab(1000.0, "y").exp();
+    assert_eq!(
+        anomaly.check().unwrap_err().to_string(),
+        "forward pass produced inf at the Exp node exp(y)"
+    );
+}
+
+#[test]
+fn is_off_without_the_guard() {
+    let a = MVal::new_lab(0.0, "a");
+    let b = MVal::new_lab(1.0, "b");
+    let mut c = b / a;
+    let anomaly = detect_anomaly();
+    assert_eq!(anomaly.check(), Ok(()));
+    // the graph built before the mode is checked by try_backward.
+    assert_eq!(
+        c.try_backward().unwrap_err().to_string(),
+        "forward pass produced inf at the Div node (b/a)"
+    );
+}
+
+#[test]
+fn detects_the_backward_anomaly() {
+    let anomaly = detect_anomaly();
+    let x = MVal::new_lab(0.0, "x");
+    let mut y = x.clone().pow(0.5) * 2.0;
+    assert_eq!(anomaly.check(), Ok(()));
+
+    // dy/dx = 0.5 * 0^-0.5 is inf.
+    let err = y.backward_custom(&BackwardOptions::default()).unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "backward pass produced inf at the Pow node pow(x,0.5)"
+    );
+    assert_eq!(anomaly.first(), Some(err));
+}
+
+#[test]
+fn try_backward_reports_the_backward_anomaly() {
+    let x = MVal::new_lab(0.0, "x");
+    let mut y = x.clone().pow(0.5) * 2.0;
+    assert_eq!(
+        y.try_backward().unwrap_err().to_string(),
+        "backward pass produced inf at the Pow node pow(x,0.5)"
+    );
+    // the gradients computed before the error are kept.
+    assert_eq!(y.grad(), 1.0);
+}
@@ -0,0 +1,150 @@
+use std::path::PathBuf;
+
+use rand::{Rng, SeedableRng};
+
+use crate::{
+    checkpoint::{ChaCha8Rng, Checkpoint},
+    optim::{LinearDecay, Sgd},
+    Error, MVal, MLP,
+};
+
+const STEPS: usize = 20;
+const CHECKPOINT_STEP: usize = 9;
+
+// unique per test, since the tests run in parallel.
+fn checkpoint_path(name: &str) -> PathBuf {
+    std::env::temp_dir().join(format!("micrograd-{name}-{}.txt", std::process::id()))
+}
+
+fn samples() -> Vec<(Vec<f64>, f64)> {
+    (0..16)
+        .map(|i| {
+            let x = i as f64 / 8.0 - 1.0;
+            let y = (i * 7 % 16) as f64 / 8.0 - 1.0;
+            (vec![x, y], if x * y > 0.0 { 1.0 } else { -1.0 })
+        })
+        .collect()
+}
+
+// one step of training on a random mini batch with svm "max-margin" loss.
+fn train_step(
+    model: &mut MLP,
+    optimizer: &mut Sgd,
+    scheduler: &mut LinearDecay,
+    rng: &mut ChaCha8Rng,
+) {
+    let samples = samples();
+    let mut loss = MVal::new(0.0);
+    for _ in 0..4 {
+        let (inp, out) = &samples[rng.gen_range(0..samples.len())];
+        let pre = model.forward(inp.clone())[0].clone();
+        loss = loss + (1.0 - pre * *out).relu();
+    }
+    let mut loss = loss / 4.0;
+    loss.zero_grad();
+    loss.backward().unwrap();
+    optimizer.step(&model.parameters(), scheduler.learning_rate());
+    scheduler.step();
+}
+
+#[test]
+fn resumed_training_is_bit_identical() {
+    let path = checkpoint_path("resume");
+    let mut model = MLP::new(2, vec![4, 4, 1]);
+    let mut optimizer = Sgd::new(0.9);
+    let mut scheduler = LinearDecay::new(0.1, 0.01, STEPS);
+    let mut rng = ChaCha8Rng::seed_from_u64(42);
+    for step in 0..STEPS {
+        train_step(&mut model, &mut optimizer, &mut scheduler, &mut rng);
+        if step == CHECKPOINT_STEP {
+            Checkpoint::new(&mut model, &optimizer, &scheduler, &rng, step, 0)
+                .save(&path)
+                .unwrap();
+        }
+    }
+
+    // new model with different initial weights resumed from the checkpoint.
+    let mut resumed = MLP::new(2, vec![4, 4, 1]);
+    let mut optimizer = Sgd::new(0.9);
+    let mut scheduler = LinearDecay::new(0.1, 0.01, STEPS);
+    let checkpoint = Checkpoint::load(&path).unwrap();
+    std::fs::remove_file(&path).unwrap();
+    let mut rng = checkpoint
+        .restore(&mut resumed, &mut optimizer, &mut scheduler)
+        .unwrap();
+    assert_eq!(checkpoint.step(), CHECKPOINT_STEP);
+    for _ in checkpoint.step() + 1..STEPS {
+        train_step(&mut resumed, &mut optimizer, &mut scheduler, &mut rng);
+    }
+
+    let bits = |model: &mut MLP| -> Vec<u64> {
+        model
+            .parameters()
+            .iter()
+            .map(|p| p.get().to_bits())
+            .collect()
+    };
+    assert_eq!(bits(&mut resumed), bits(&mut model));
+}
+
+#[test]
+fn restore_rejects_a_model_of_other_size() {
+    let mut model = MLP::new(2, vec![4, 1]);
+    let rng = ChaCha8Rng::seed_from_u64(42);
+    let checkpoint = Checkpoint::new(
+        &mut model,
+        &Sgd::new(0.9),
+        &LinearDecay::new(0.1, 0.01, STEPS),
+        &rng,
+        0,
+        0,
+    );
+
+    let mut other = MLP::new(2, vec![8, 1]);
+    let result = checkpoint.restore(
+        &mut other,
+        &mut Sgd::new(0.9),
+        &mut LinearDecay::new(0.1, 0.01, STEPS),
+    );
+    assert!(matches!(result, Err(Error::Checkpoint(_))));
+}
+
+#[test]
+fn restore_rejects_a_velocity_of_other_size() {
+    // the optimizer took a step on the parameters of an other model.
+    let mut optimizer = Sgd::new(0.9);
+    optimizer.step(&MLP::new(2, vec![8, 1]).parameters(), 0.1);
+
+    let mut model = MLP::new(2, vec![4, 1]);
+    let rng = ChaCha8Rng::seed_from_u64(42);
+    let checkpoint = Checkpoint::new(
+        &mut model,
+        &optimizer,
+        &LinearDecay::new(0.1, 0.01, STEPS),
+        &rng,
+        0,
+        0,
+    );
+    let result = checkpoint.restore(
+        &mut model,
+        &mut Sgd::new(0.9),
+        &mut LinearDecay::new(0.1, 0.01, STEPS),
+    );
+    assert!(matches!(result, Err(Error::Checkpoint(_))));
+
+    // a checkpoint taken before the first step has no velocity.
+    let checkpoint = Checkpoint::new(
+        &mut model,
+        &Sgd::new(0.9),
+        &LinearDecay::new(0.1, 0.01, STEPS),
+        &rng,
+        0,
+        0,
+    );
+    let result = checkpoint.restore(
+        &mut model,
+        &mut Sgd::new(0.9),
+        &mut LinearDecay::new(0.1, 0.01, STEPS),
+    );
+    assert!(result.is_ok());
+}
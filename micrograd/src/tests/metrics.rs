@@ -0,0 +1,68 @@
+use crate::metrics::{confusion_matrix, per_class_accuracy, r2, roc_auc};
+
+#[test]
+fn roc_auc_counts_ties_as_half() {
+    // positives 0.4 and 0.8 against negatives 0.1 and 0.4: 1 + 0.5 + 1 + 1 out of 4 pairs.
+    let scores = [0.1, 0.4, 0.4, 0.8];
+    let actual = [-1.0, 1.0, -1.0, 1.0];
+    assert_eq!(roc_auc(&scores, &actual, 1.0), 0.875);
+}
+
+#[test]
+fn roc_auc_of_degenerate_inputs() {
+    let actual = [-1.0, 1.0, -1.0, 1.0];
+    assert_eq!(roc_auc(&[0.1, 0.3, 0.2, 0.4], &actual, 1.0), 1.0);
+    assert_eq!(roc_auc(&[0.4, 0.1, 0.3, 0.2], &actual, 1.0), 0.0);
+    // all the predictions are equal.
+    assert_eq!(roc_auc(&[0.3; 4], &actual, 1.0), 0.5);
+    // single class.
+    assert_eq!(roc_auc(&[0.1, 0.2, 0.3], &[1.0; 3], 1.0), 0.5);
+    assert_eq!(roc_auc(&[0.1, 0.2, 0.3], &[-1.0; 3], 1.0), 0.5);
+    assert_eq!(roc_auc(&[], &[], 1.0), 0.5);
+}
+
+#[test]
+fn r2_of_fits() {
+    let actual = [1.0, 2.0, 3.0];
+    assert_eq!(r2(&actual, &actual), 1.0);
+    // always predicting the mean.
+    assert_eq!(r2(&[2.0; 3], &actual), 0.0);
+    // residual sum of squares 1 and total sum of squares 2.
+    assert_eq!(r2(&[1.0, 2.0, 4.0], &actual), 0.5);
+    assert!(r2(&[3.0, 2.0, 1.0], &actual) < 0.0);
+}
+
+#[test]
+fn r2_of_degenerate_inputs() {
+    // all the actual values are equal.
+    assert_eq!(r2(&[5.0; 3], &[5.0; 3]), 1.0);
+    assert_eq!(r2(&[5.0, 5.0, 6.0], &[5.0; 3]), 0.0);
+    assert_eq!(r2(&[], &[]), 0.0);
+}
+
+#[test]
+fn confusion_matrix_counts_actual_by_predicted() {
+    let matrix = confusion_matrix(&[0, 1, 1, 2], &[0, 1, 2, 2], 3);
+    assert_eq!(matrix, vec![vec![1, 0, 0], vec![0, 1, 0], vec![0, 1, 1]]);
+}
+
+#[test]
+fn confusion_matrix_of_a_single_class() {
+    let matrix = confusion_matrix(&[0, 0, 0], &[0, 0, 0], 2);
+    assert_eq!(matrix, vec![vec![3, 0], vec![0, 0]]);
+    assert_eq!(
+        per_class_accuracy(&[0, 0, 0], &[0, 0, 0], 2),
+        vec![1.0, 0.0]
+    );
+    // all the predictions are equal.
+    assert_eq!(
+        per_class_accuracy(&[1, 1, 1, 1], &[0, 1, 0, 1], 2),
+        vec![0.0, 1.0]
+    );
+}
+
+#[test]
+#[should_panic(expected = "expecting classes less than 2")]
+fn confusion_matrix_rejects_classes_out_of_range() {
+    confusion_matrix(&[0, 2], &[0, 1], 2);
+}
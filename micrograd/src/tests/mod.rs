@@ -0,0 +1,176 @@
+// The derivative rules are implemented separately by each engine(MVal::backward, backward_graph,
+// AVal and Tape). These tests build the same expression with all of them, so a change to the
+// rules of one engine can't silently drift from the others.
+
+mod anomaly;
+mod checkpoint;
+mod metrics;
+
+use crate::{functional::hessian, AVal, BackwardOptions, Error, MVal, Tape};
+
+const X: f64 = 0.7;
+const Y: f64 = 1.3;
+
+// uses the operands on both sides of the operations(x*x, x-x, y/y, x+x) to cover the aliasing.
+// works for MVal, AVal and Var since all of them are Clone and support the same operations.
+macro_rules! expression {
+    ($x:expr, $y:expr) => {{
+        let (x, y) = ($x, $y);
+        x.clone() * x.clone()
+            + (x.clone() - x.clone())
+            + y.clone() / y.clone()
+            + (x.clone() * y.clone()).tanh()
+            + x.clone().pow(3.0) / y.clone()
+            + (x.clone() + x.clone()) * y.clone().exp()
+            + (x.clone() - y.clone()).relu()
+    }};
+}
+
+/// df/dx and df/dy of the expression derived by hand. relu(x - y) is 0 since x < y.
+fn expected_grads(x: f64, y: f64) -> [f64; 2] {
+    let dtanh = 1.0 - (x * y).tanh().powi(2);
+    [
+        2.0 * x + y * dtanh + 3.0 * x * x / y + 2.0 * y.exp(),
+        x * dtanh - x.powi(3) / (y * y) + 2.0 * x * y.exp(),
+    ]
+}
+
+fn assert_close(actual: &[f64], expected: &[f64], tolerance: f64) {
+    assert_eq!(actual.len(), expected.len());
+    for (a, e) in actual.iter().zip(expected) {
+        assert!((a - e).abs() < tolerance, "{actual:?} != {expected:?}");
+    }
+}
+
+#[test]
+fn backward_matches_derived_gradients() {
+    let (x, y) = (MVal::new(X), MVal::new(Y));
+    let mut out = expression!(x.clone(), y.clone());
+    out.backward().unwrap();
+    assert_close(&[x.grad(), y.grad()], &expected_grads(X, Y), 1e-12);
+}
+
+#[test]
+fn engines_agree() {
+    let (x, y) = (MVal::new(X), MVal::new(Y));
+    let mut out = expression!(x.clone(), y.clone());
+    // backward_graph walks the same graph below.
+    out.backward_custom(&BackwardOptions { retain_graph: true })
+        .unwrap();
+    let expected = [x.grad(), y.grad()];
+
+    let (ax, ay) = (AVal::new(X), AVal::new(Y));
+    let a_out = expression!(ax.clone(), ay.clone());
+    a_out.backward();
+    assert_eq!(a_out.get(), out.get());
+    assert_close(&[ax.grad(), ay.grad()], &expected, 1e-12);
+
+    let tape = Tape::new();
+    let (tx, ty) = (tape.var(X), tape.var(Y));
+    let t_out = expression!(tx, ty);
+    tape.backward(t_out);
+    assert_eq!(t_out.get(), out.get());
+    assert_close(&[tx.grad(), ty.grad()], &expected, 1e-12);
+
+    let graph: Vec<f64> = out
+        .backward_graph(&[x.clone(), y.clone()])
+        .unwrap()
+        .iter()
+        .map(|g| g.get())
+        .collect();
+    assert_close(&graph, &expected, 1e-12);
+}
+
+#[test]
+fn hessian_matches_finite_differences_of_the_gradients() {
+    let h = 1e-5;
+    let actual = hessian(|v| expression!(v[0].clone(), v[1].clone()), &[X, Y]).unwrap();
+    let by_x: Vec<f64> = (0..2)
+        .map(|i| (expected_grads(X + h, Y)[i] - expected_grads(X - h, Y)[i]) / (2.0 * h))
+        .collect();
+    let by_y: Vec<f64> = (0..2)
+        .map(|i| (expected_grads(X, Y + h)[i] - expected_grads(X, Y - h)[i]) / (2.0 * h))
+        .collect();
+    assert_close(&actual[0], &[by_x[0], by_y[0]], 1e-6);
+    assert_close(&actual[1], &[by_x[1], by_y[1]], 1e-6);
+}
+
+#[test]
+fn hessian_of_a_simple_expression() {
+    // f = x^2 * y + tanh(y)
+    let actual = hessian(
+        |v| v[0].clone() * v[0].clone() * v[1].clone() + v[1].clone().tanh(),
+        &[X, Y],
+    )
+    .unwrap();
+    let t = Y.tanh();
+    assert_close(&actual[0], &[2.0 * Y, 2.0 * X], 1e-12);
+    assert_close(&actual[1], &[2.0 * X, -2.0 * t * (1.0 - t * t)], 1e-12);
+}
+
+#[test]
+fn backward_through_a_shared_freed_graph_fails() {
+    let w = MVal::new(X);
+    let h = (w.clone() * 2.0).tanh();
+    let mut l1 = h.clone() * 3.0;
+    let mut l2 = h.clone() * 4.0;
+    l1.backward().unwrap();
+    assert!(h.is_freed());
+
+    w.set_grad(MVal::new(0.0));
+    assert_eq!(
+        l2.backward_custom(&BackwardOptions { retain_graph: true }),
+        Err(Error::GraphFreed)
+    );
+    assert_eq!(l2.try_backward(), Err(Error::GraphFreed));
+    assert_eq!(
+        l2.backward_graph(std::slice::from_ref(&w)).err(),
+        Some(Error::GraphFreed)
+    );
+    assert_eq!(w.grad(), 0.0);
+}
+
+#[test]
+fn backward_twice_returns_graph_freed() {
+    let x = MVal::new(2.0);
+    let mut y = x.clone() * x.clone();
+    y.backward().unwrap();
+    assert_eq!(x.grad(), 4.0);
+    assert_eq!(y.backward(), Err(Error::GraphFreed));
+    assert_eq!(y.backward_debug(), Err(Error::GraphFreed));
+    assert_eq!(x.grad(), 4.0);
+}
+
+#[test]
+fn retained_graph_accumulates_the_gradients() {
+    let x = MVal::new(2.0);
+    let mut y = x.clone() * x.clone();
+    let retain = BackwardOptions { retain_graph: true };
+    y.backward_custom(&retain).unwrap();
+    y.backward_custom(&retain).unwrap();
+    assert_eq!(x.grad(), 8.0);
+    // the last backward can free the graph.
+    y.backward().unwrap();
+    assert_eq!(x.grad(), 12.0);
+    assert!(y.is_freed());
+}
+
+#[test]
+fn backward_does_not_free_the_graph_under_stop_gradient() {
+    let x = MVal::new(X);
+    let r = MVal::new(Y);
+    let d = r.clone() - x.clone();
+    let blocked = d.clone().stop_gradient();
+    let mut y = x.clone() * 2.0 + blocked.clone();
+    y.backward().unwrap();
+    assert!(y.is_freed());
+    assert!(!blocked.is_freed() && !d.is_freed());
+    assert_eq!((x.grad(), r.grad()), (2.0, 0.0));
+    assert_eq!(y.backward(), Err(Error::GraphFreed));
+
+    // the graph under stop_gradient is still usable by the other Values.
+    let mut z = d * 3.0;
+    z.backward().unwrap();
+    assert_eq!((x.grad(), r.grad()), (-1.0, 3.0));
+    assert!(z.is_freed());
+}
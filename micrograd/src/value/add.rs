@@ -1,80 +1,63 @@
 use super::{
-    data_type::{DataType, IntoValue},
-    MVal, Operator, Value,
+    data_type::{Float, IntoValue},
+    MVal, Operator,
 };
-use std::{cell::RefCell, ops::Add, rc::Rc};
+use std::ops::Add;
 
 // MVal + MVal
-impl Add for MVal {
-    type Output = MVal;
+impl<F: Float> Add for MVal<F> {
+    type Output = MVal<F>;
 
     fn add(self, rhs: Self) -> Self::Output {
         let lhsv = self.0.borrow();
         let rhsv = rhs.0.borrow();
 
-        MVal(Rc::new(RefCell::new(Value {
-            data: lhsv.data + rhsv.data,
-            grad: 0.0,
-            operands: vec![self.clone(), rhs.clone()],
-            operator: Operator::Plus,
-            label: format!("({}+{})", lhsv.label, rhsv.label),
-        })))
+        MVal::from_op(
+            lhsv.data + rhsv.data,
+            vec![self.clone(), rhs.clone()],
+            Operator::Plus,
+        )
     }
 }
 
-// lhs needs to be cloned if it needs to be reused after the operation.
-// DataType + MVal
-impl Add<MVal> for DataType {
-    type Output = MVal;
-
-    fn add(self, rhs: MVal) -> Self::Output {
-        let lhsv = self;
-        let lhs = MVal(Rc::new(RefCell::new(Value {
-            data: lhsv,
-            grad: 0.0,
-            operands: vec![],
-            operator: Operator::None,
-            label: String::new(),
-        })));
-
-        let rhsv = rhs.0.borrow();
-
-        MVal(Rc::new(RefCell::new(Value {
-            data: lhsv + rhsv.data,
-            grad: 0.0,
-            operands: vec![lhs, rhs.clone()],
-            operator: Operator::Plus,
-            label: format!("({}+{})", lhsv, rhsv.label),
-        })))
-    }
+// the operations with constants are implemented for each Float type separately, so that
+// the DataType op MVal impls can be written for both f32 and f64.
+macro_rules! impl_add {
+    ($t:ty) => {
+        // lhs needs to be cloned if it needs to be reused after the operation.
+        // DataType + MVal
+        impl Add<MVal<$t>> for $t {
+            type Output = MVal<$t>;
+
+            fn add(self, rhs: MVal<$t>) -> Self::Output {
+                let lhsv = self;
+                let lhs = MVal::constant(lhsv);
+
+                let rhsv = rhs.0.borrow();
+
+                MVal::from_op(lhsv + rhsv.data, vec![lhs, rhs.clone()], Operator::Plus)
+            }
+        }
+
+        // MVal + T
+        impl<T> Add<T> for MVal<$t>
+        where
+            T: IntoValue<$t>,
+        {
+            type Output = MVal<$t>;
+
+            fn add(self, rhs: T) -> Self::Output {
+                let lhs = self;
+                let lhsv = lhs.0.borrow();
+
+                let rhsv = rhs.into_value();
+                let rhs = MVal::constant(rhsv);
+
+                MVal::from_op(lhsv.data + rhsv, vec![lhs.clone(), rhs], Operator::Plus)
+            }
+        }
+    };
 }
 
-// MVal + T
-impl<T> Add<T> for MVal
-where
-    T: IntoValue,
-{
-    type Output = MVal;
-
-    fn add(self, rhs: T) -> Self::Output {
-        let lhs = self;
-        let lhsv = lhs.0.borrow();
-
-        let rhsv = rhs.into_value();
-        let rhs = MVal(Rc::new(RefCell::new(Value {
-            data: rhsv,
-            grad: 0.0,
-            operands: vec![],
-            operator: Operator::None,
-            label: String::new(),
-        })));
-
-        MVal(Rc::new(RefCell::new(Value {
-            data: lhsv.data + rhsv,
-            grad: 0.0,
-            operands: vec![lhs.clone(), rhs],
-            operator: Operator::Plus,
-            label: format!("({}+{})", lhsv.label, rhsv),
-        })))
-    }
-}
+impl_add!(f32);
+impl_add!(f64);
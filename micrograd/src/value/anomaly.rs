@@ -0,0 +1,152 @@
+use std::{
+    cell::{Cell, RefCell},
+    marker::PhantomData,
+    rc::Rc,
+};
+
+use super::{data_type::Float, BackwardOptions, MVal, Operator, Value};
+use crate::error::{Error, Result};
+
+/*
+What is the anomaly detection?
+    - Operations like exp of a large number, pow of a negative number with a fractional power
+      or division by zero produce inf or NaN. Once a data or gradient is not finite, it silently
+      spreads to every Value depending on it and to the gradient of every parameter.
+
+    - `detect_anomaly` turns on the anomaly detection mode until the returned guard is dropped.
+      While it is on, each operation checks its data as soon as it is created and backward checks
+      the gradients of the operands of each node as soon as they are computed. The first node that
+      produced a non-finite value is recorded in the guard, and backward returns it as error.
+      The mode is per thread, like the graph of the MVal.
+
+    - `check_finite` walks an already built graph in the topological order(operands before the
+      operations) and returns the first node whose data is not finite. All of its operands are
+      finite, so it is the node that produced the anomaly.
+
+    - `try_backward` is backward in the anomaly detection mode after `check_finite`, for
+      the graphs built while the mode was off.
+
+How to Use it?
+    - Turn on the mode while debugging a training that ends up with NaN. It is slower, since each
+      node is checked, but computes exactly the same data and gradients.
+            `let anomaly = detect_anomaly();
+             let mut loss = forward(...);
+             anomaly.check()?; // forward pass produced inf at the Div node (b/a)
+             loss.backward()?; // backward pass produced inf at the Pow node pow(x,0.5)`
+*/
+
+// levels of the operands rendered in the label of the error.
+const ANOMALY_LABEL_DEPTH: usize = 2;
+
+thread_local! {
+    // number of live AnomalyMode guards of the thread.
+    static DETECTING: Cell<usize> = const { Cell::new(0) };
+    // the first anomaly found since the mode was turned on.
+    static FIRST_ANOMALY: RefCell<Option<Error>> = const { RefCell::new(None) };
+}
+
+/// guard of the anomaly detection mode. The mode is turned off when all the guards are dropped.
+pub struct AnomalyMode {
+    // the mode is per thread, so the guard must be dropped on the thread that created it.
+    _not_send: PhantomData<Rc<()>>,
+}
+
+/// turns on the anomaly detection mode of the current thread until the guard is dropped.
+/// The anomaly recorded by the previous mode is cleared, unless an other guard is still alive.
+pub fn detect_anomaly() -> AnomalyMode {
+    DETECTING.with(|detecting| {
+        if detecting.get() == 0 {
+            FIRST_ANOMALY.with(|first| *first.borrow_mut() = None);
+        }
+        detecting.set(detecting.get() + 1);
+    });
+    AnomalyMode {
+        _not_send: PhantomData,
+    }
+}
+
+impl AnomalyMode {
+    /// the first node that produced a non-finite data or gradient while the mode was on.
+    pub fn first(&self) -> Option<Error> {
+        FIRST_ANOMALY.with(|first| first.borrow().clone())
+    }
+
+    /// returns the first anomaly as error.
+    pub fn check(&self) -> Result<()> {
+        self.first().map_or(Ok(()), Err)
+    }
+}
+
+impl Drop for AnomalyMode {
+    fn drop(&mut self) {
+        DETECTING.with(|detecting| detecting.set(detecting.get() - 1));
+    }
+}
+
+pub(super) fn is_detecting() -> bool {
+    DETECTING.with(|detecting| detecting.get() > 0)
+}
+
+/// keeps the error if it is the first anomaly since the mode was turned on.
+fn record(err: &Error) {
+    FIRST_ANOMALY.with(|first| {
+        first.borrow_mut().get_or_insert_with(|| err.clone());
+    });
+}
+
+impl<F: Float> MVal<F> {
+    /// returns error with the first node of the graph that has a non-finite data.
+    pub fn check_finite(&self) -> Result<()> {
+        for node in self.collect_operands() {
+            let val = node.0.borrow();
+            if !val.data.is_finite() {
+                return Err(val.anomaly("forward", val.data));
+            }
+        }
+        Ok(())
+    }
+
+    /// Same as backward in the anomaly detection mode, but also checks the data of the graph
+    /// built before the mode was turned on. The gradients computed before the error are kept.
+    /// returns error without computing any gradient if the graph was freed by backward_custom.
+    pub fn try_backward(&mut self) -> Result<()> {
+        self.check_finite()?;
+        let _anomaly = detect_anomaly();
+        self.backward_custom(&BackwardOptions::default())
+    }
+}
+
+impl<F: Float> Value<F> {
+    /// records the operation in the anomaly detection mode if its data is not finite.
+    pub(super) fn check_forward(&self) {
+        if !self.data.is_finite() && is_detecting() {
+            record(&self.anomaly("forward", self.data));
+        }
+    }
+
+    /// returns error with the node if the gradient of any of its operands is not finite.
+    pub(super) fn check_backward(&self) -> Result<()> {
+        let grads: Vec<F> = self.operands.iter().map(|op| op.grad()).collect();
+        match grads.into_iter().find(|g| !g.is_finite()) {
+            Some(grad) => {
+                let err = self.anomaly("backward", grad);
+                record(&err);
+                Err(err)
+            }
+            None => Ok(()),
+        }
+    }
+
+    fn anomaly(&self, stage: &'static str, value: F) -> Error {
+        let operator = match self.operator {
+            Operator::None => "leaf".to_string(),
+            op => format!("{op:?}"),
+        };
+        Error::NonFinite {
+            stage,
+            label: self.render_shallow(ANOMALY_LABEL_DEPTH),
+            operator,
+            value: value.to_f64(),
+        }
+    }
+}
@@ -1,14 +1,99 @@
+use std::{
+    fmt::{Debug, Display},
+    ops::{Add, AddAssign, Div, Mul, Neg, Sub},
+};
+
+/*
+What is Float?
+    - Float is the type of the `data` and `grad` fields of a Value. It is implemented for
+      f64(DataType, the default) and f32. A model in f32 takes half the memory of a model in f64
+      at the cost of precision.
+
+    - MVal, Neuron, Layer and MLP are generic over it with f64 as the default type. So `MVal`
+      is the same as `MVal<f64>` and `MVal::new(2)` always creates an f64 Value. Use the
+      `new_typed` constructors to create Values or models of an other Float type.
+            `let x = MVal::<f32>::new_typed(2.0);
+             let model = MLP::<f32>::new_typed(2, vec![16, 16, 1]);`
+*/
+
 pub type DataType = f64;
-pub trait IntoValue {
-    fn into_value(self) -> DataType;
+
+pub trait Float:
+    Copy
+    + Default
+    + PartialOrd
+    + Debug
+    + Display
+    + Add<Output = Self>
+    + Sub<Output = Self>
+    + Mul<Output = Self>
+    + Div<Output = Self>
+    + Neg<Output = Self>
+    + AddAssign
+    + 'static
+{
+    fn zero() -> Self;
+    fn one() -> Self;
+    /// converts the f64 value to Self. Might lose the precision.
+    fn from_f64(val: f64) -> Self;
+    fn to_f64(self) -> f64;
+    fn tanh(self) -> Self;
+    fn exp(self) -> Self;
+    /// natural logarithm.
+    fn ln(self) -> Self;
+    fn powf(self, n: Self) -> Self;
+    /// false for inf, -inf and NaN.
+    fn is_finite(self) -> bool;
+}
+
+macro_rules! impl_float {
+    ($t:ty) => {
+        impl Float for $t {
+            fn zero() -> Self {
+                0.0
+            }
+            fn one() -> Self {
+                1.0
+            }
+            fn from_f64(val: f64) -> Self {
+                val as $t
+            }
+            fn to_f64(self) -> f64 {
+                self as f64
+            }
+            fn tanh(self) -> Self {
+                <$t>::tanh(self)
+            }
+            fn exp(self) -> Self {
+                <$t>::exp(self)
+            }
+            fn ln(self) -> Self {
+                <$t>::ln(self)
+            }
+            fn powf(self, n: Self) -> Self {
+                <$t>::powf(self, n)
+            }
+            fn is_finite(self) -> bool {
+                <$t>::is_finite(self)
+            }
+        }
+    };
+}
+
+impl_float!(f32);
+impl_float!(f64);
+
+pub trait IntoValue<F: Float = DataType> {
+    fn into_value(self) -> F;
 }
 
-// Blanket implementation for all types that can convert into DataType
-impl<T> IntoValue for T
+// Blanket implementation for all types that can convert into the Float type
+impl<T, F> IntoValue<F> for T
 where
-    T: Into<DataType>,
+    F: Float,
+    T: Into<F>,
 {
-    fn into_value(self) -> DataType {
+    fn into_value(self) -> F {
         self.into()
     }
 }
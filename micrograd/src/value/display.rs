@@ -1,36 +1,111 @@
 use std::fmt::{Debug, Display};
 
-use super::{MVal, Value};
+use super::{data_type::Float, MVal, Operator, Value};
 
-impl Display for MVal {
+/*
+How are the labels rendered?
+    - Only the leaves created with `new_lab` store a label. Operations don't format and store the
+      labels of their operands, because the labels grow quadratically along a long chain of
+      operations(like the summed loss) and take a huge amount of memory.
+
+    - Instead the label of an operation is rendered from its operator and operands only when it
+      is needed by `Debug` or `label`. Leaves without a label are rendered with their data.
+
+    - The errors(like the anomaly detection) render only a few levels below the Value, the deeper
+      operations are rendered as `..`. Otherwise the label of a loss would have the whole graph.
+*/
+
+impl<F: Float> Value<F> {
+    /// renders the label of the Value from its operator and operands.
+    pub(super) fn render_label(&self) -> String {
+        self.render(None)
+    }
+
+    /// same as render_label but the operations more than depth levels below the Value are
+    /// rendered as `..`, so the length of the label doesn't grow with the size of the graph.
+    pub(super) fn render_shallow(&self, depth: usize) -> String {
+        self.render(Some(depth))
+    }
+
+    fn render(&self, depth: Option<usize>) -> String {
+        let operand = |i: usize| {
+            self.operands
+                .get(i)
+                .map(|op| {
+                    let op = op.0.borrow();
+                    match (&op.operator, depth) {
+                        (Operator::None, _) => op.render_operand(None),
+                        (_, Some(0)) => "..".to_string(),
+                        _ => op.render_operand(depth.map(|d| d - 1)),
+                    }
+                })
+                .unwrap_or_default()
+        };
+        match self.operator {
+            Operator::None => self.label.clone(),
+            Operator::Plus => format!("({}+{})", operand(0), operand(1)),
+            Operator::Minus => format!("({}-{})", operand(0), operand(1)),
+            Operator::Mul => format!("({}*{})", operand(0), operand(1)),
+            Operator::Div => format!("({}/{})", operand(0), operand(1)),
+            Operator::Tanh => format!("tanh({})", operand(0)),
+            Operator::ReLU => format!("ReLU({})", operand(0)),
+            Operator::Exp => format!("exp({})", operand(0)),
+            Operator::Log => format!("log({})", operand(0)),
+            Operator::Pow => format!("pow({},{})", operand(0), operand(1)),
+            Operator::StopGradient => format!("stop_gradient({})", operand(0)),
+        }
+    }
+
+    /// same as render but leaves without a label(like constants) are rendered with their data.
+    fn render_operand(&self, depth: Option<usize>) -> String {
+        match self.operator {
+            Operator::None if self.label.is_empty() => self.data.to_string(),
+            _ => self.render(depth),
+        }
+    }
+}
+
+impl<F: Float> MVal<F> {
+    /// returns the label of leaves created with `new_lab` or
+    /// the expression that computed the Value for other Values.
+    pub fn label(&self) -> String {
+        self.0.borrow().render_label()
+    }
+}
+
+impl<F: Float> Display for MVal<F> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(f, "Value({})", self.0.borrow().data)
     }
 }
 
-impl Debug for MVal {
+impl<F: Float> Debug for MVal<F> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         let val = self.0.borrow();
         write!(
             f,
             "Value(data:{}, grad:{}, {})",
-            val.data, val.grad, val.label,
+            val.data,
+            val.grad,
+            val.render_label(),
         )
     }
 }
 
-impl Display for Value {
+impl<F: Float> Display for Value<F> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(f, "Value({})", self.data)
     }
 }
 
-impl Debug for Value {
+impl<F: Float> Debug for Value<F> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(
             f,
             "Value(data:{}, grad:{}, {})",
-            self.data, self.grad, self.label,
+            self.data,
+            self.grad,
+            self.render_label(),
         )
     }
 }
@@ -1,79 +1,60 @@
 use super::{
-    data_type::{DataType, IntoValue},
-    MVal, Operator, Value,
+    data_type::{Float, IntoValue},
+    MVal, Operator,
 };
-use std::{cell::RefCell, ops::Div, rc::Rc};
+use std::ops::Div;
 
 // MVal / MVal
-impl Div for MVal {
-    type Output = MVal;
+impl<F: Float> Div for MVal<F> {
+    type Output = MVal<F>;
 
     fn div(self, rhs: Self) -> Self::Output {
         let lhsv = self.0.borrow();
         let rhsv = rhs.0.borrow();
 
-        MVal(Rc::new(RefCell::new(Value {
-            data: lhsv.data / rhsv.data,
-            grad: 0.0,
-            operands: vec![self.clone(), rhs.clone()],
-            operator: Operator::Div,
-            label: format!("({}/{})", lhsv.label, rhsv.label),
-        })))
+        MVal::from_op(
+            lhsv.data / rhsv.data,
+            vec![self.clone(), rhs.clone()],
+            Operator::Div,
+        )
     }
 }
 
-// DataType / MVal
-impl Div<MVal> for DataType {
-    type Output = MVal;
+macro_rules! impl_div {
+    ($t:ty) => {
+        // DataType / MVal
+        impl Div<MVal<$t>> for $t {
+            type Output = MVal<$t>;
 
-    fn div(self, rhs: MVal) -> Self::Output {
-        let lhsv = self;
-        let lhs = MVal(Rc::new(RefCell::new(Value {
-            data: lhsv,
-            grad: 0.0,
-            operands: vec![],
-            operator: Operator::None,
-            label: String::new(),
-        })));
+            fn div(self, rhs: MVal<$t>) -> Self::Output {
+                let lhsv = self;
+                let lhs = MVal::constant(lhsv);
 
-        let rhsv = rhs.0.borrow();
+                let rhsv = rhs.0.borrow();
 
-        MVal(Rc::new(RefCell::new(Value {
-            data: lhsv / rhsv.data,
-            grad: 0.0,
-            operands: vec![lhs, rhs.clone()],
-            operator: Operator::Div,
-            label: format!("({}/{})", lhsv, rhsv.label),
-        })))
-    }
-}
+                MVal::from_op(lhsv / rhsv.data, vec![lhs, rhs.clone()], Operator::Div)
+            }
+        }
 
-// MVal / T
-impl<T> Div<T> for MVal
-where
-    T: IntoValue,
-{
-    type Output = MVal;
+        // MVal / T
+        impl<T> Div<T> for MVal<$t>
+        where
+            T: IntoValue<$t>,
+        {
+            type Output = MVal<$t>;
 
-    fn div(self, rhs: T) -> Self::Output {
-        let lhs = self;
-        let lhsv = lhs.0.borrow();
+            fn div(self, rhs: T) -> Self::Output {
+                let lhs = self;
+                let lhsv = lhs.0.borrow();
 
-        let rhsv = rhs.into_value();
-        let rhs = MVal(Rc::new(RefCell::new(Value {
-            data: rhsv,
-            grad: 0.0,
-            operands: vec![],
-            operator: Operator::None,
-            label: String::new(),
-        })));
+                let rhsv = rhs.into_value();
+                let rhs = MVal::constant(rhsv);
 
-        MVal(Rc::new(RefCell::new(Value {
-            data: lhsv.data / rhsv,
-            grad: 0.0,
-            operands: vec![lhs.clone(), rhs],
-            operator: Operator::Div,
-            label: format!("({}/{})", lhsv.label, rhsv),
-        })))
-    }
+                MVal::from_op(lhsv.data / rhsv, vec![lhs.clone(), rhs], Operator::Div)
+            }
+        }
+    };
 }
+
+impl_div!(f32);
+impl_div!(f64);
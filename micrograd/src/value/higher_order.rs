@@ -0,0 +1,121 @@
+use std::collections::HashMap;
+
+use super::{data_type::Float, MVal, Operator};
+use crate::error::{Error, Result};
+
+/*
+How are the higher order gradients computed?
+    - `backward` stores the gradient of each Value as a raw number in the `grad` field, so the
+      gradient itself can't be differentiated again.
+
+    - `backward_graph` follows the same chain rule in the same topological order, but the gradient
+      of each Value is built with MVal operations on top of the original graph. For example the
+      gradient of y = x * z w.r.t x is the MVal node `out_grad * z` instead of the number.
+
+    - Since the returned gradients are ordinary MVal nodes depending on the original inputs, we can
+      call `backward` or `backward_graph` on them again to get the second order gradients.
+            `let y = x.clone() * x.clone() * x.clone();
+             let dx = y.backward_graph(&[x.clone()])?[0].clone(); // 3x^2
+             let dxx = dx.backward_graph(&[x.clone()])?[0].clone(); // 6x`
+
+    - The `grad` fields are never touched, so there is no need to call zero_grad before it.
+*/
+
+impl<F: Float> MVal<F> {
+    /// Same as backward but returns the gradient of the calling Value w.r.t each Value in `wrt`
+    /// as MVal nodes, which can be differentiated again.
+    /// Values that the calling Value is not depending on will get a constant 0 gradient.
+    /// returns error if any part of the graph was freed by backward_custom.
+    pub fn backward_graph(&self, wrt: &[MVal<F>]) -> Result<Vec<MVal<F>>> {
+        let mut all_nodes = self.collect_operands();
+        if all_nodes.iter().any(|node| node.is_freed()) {
+            return Err(Error::GraphFreed);
+        }
+        all_nodes.reverse();
+
+        // gradient with itself is 1.
+        let mut grads = HashMap::new();
+        grads.insert(self.clone(), MVal::constant(F::one()));
+        for node in all_nodes {
+            let Some(out_grad) = grads.get(&node).cloned() else {
+                continue;
+            };
+            for (op, grad) in node.operand_grads_graph(out_grad) {
+                if !op.requires_grad() {
+                    continue;
+                }
+                // accumulate the gradients of the Values used in multiple places.
+                let grad = match grads.remove(&op) {
+                    Some(acc) => acc + grad,
+                    None => grad,
+                };
+                grads.insert(op, grad);
+            }
+        }
+
+        Ok(wrt
+            .iter()
+            .map(|w| {
+                grads
+                    .get(w)
+                    .cloned()
+                    .unwrap_or_else(|| MVal::constant(F::zero()))
+            })
+            .collect())
+    }
+
+    /// returns the operands of the current node with the global gradient of each of them
+    /// built as MVal nodes. out_grad is the global gradient of the current node.
+    fn operand_grads_graph(&self, out_grad: MVal<F>) -> Vec<(MVal<F>, MVal<F>)> {
+        let val = self.0.borrow();
+        let konst = |v: F| MVal::constant(v);
+        let out = self.clone();
+        let g = out_grad;
+        match (val.operator, val.operands.as_slice()) {
+            // if y = x + z, then dy/dx = 1 and dy/dz = 1
+            (Operator::Plus, [x, z]) => vec![(x.clone(), g.clone()), (z.clone(), g)],
+            // if y = x - z, then dy/dx = 1 and dy/dz = -1
+            (Operator::Minus, [x, z]) => {
+                vec![(x.clone(), g.clone()), (z.clone(), konst(F::zero()) - g)]
+            }
+            // if y = x * z, then dy/dx = z and dy/dz = x
+            (Operator::Mul, [x, z]) => vec![
+                (x.clone(), g.clone() * z.clone()),
+                (z.clone(), g * x.clone()),
+            ],
+            // y=x/z then dy/dx = 1/z and dy/dz = -x/z^2.
+            (Operator::Div, [x, z]) => vec![
+                (x.clone(), g.clone() / z.clone()),
+                (
+                    z.clone(),
+                    konst(F::zero()) - g * x.clone() / (z.clone() * z.clone()),
+                ),
+            ],
+            // y = tahh(x) then dy/dx = 1 - (tanh(x))^2
+            (Operator::Tanh, [x]) => {
+                vec![(x.clone(), g * (konst(F::one()) - out.clone() * out))]
+            }
+            // ReLU y = max(0,x). dy/dx = 1 for x > 0. The second derivative is 0.
+            (Operator::ReLU, [x]) => {
+                let local = if val.data > F::zero() {
+                    F::one()
+                } else {
+                    F::zero()
+                };
+                vec![(x.clone(), g * konst(local))]
+            }
+            // y = exp(x) then dy/dx = exp(x)
+            (Operator::Exp, [x]) => vec![(x.clone(), g * out)],
+            // y = ln(x) then dy/dx = 1/x
+            (Operator::Log, [x]) => vec![(x.clone(), g / x.clone())],
+            // y = x^n then dy/dx = n * x^(n-1). power is a constant, so it won't get a gradient.
+            (Operator::Pow, [x, n]) => {
+                let local = n.clone() * x.clone().pow(n.get() - F::one());
+                vec![(x.clone(), g * local)]
+            }
+            // the gradient is blocked, so the operand gets nothing from this node.
+            (Operator::StopGradient, _) => vec![],
+            _ => vec![],
+        }
+    }
+}
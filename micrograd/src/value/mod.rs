@@ -1,3 +1,7 @@
+// MVal is hashed by the address of the Rc, so mutating the inner Value never changes its hash.
+// That makes the HashMaps and HashSets of MVal used to walk the graph safe.
+#![allow(clippy::mutable_key_type)]
+
 use std::{
     cell::RefCell,
     collections::HashSet,
@@ -6,14 +10,19 @@ use std::{
     rc::Rc,
 };
 
-use data_type::{DataType, IntoValue};
+use data_type::{DataType, Float, IntoValue};
+
+use crate::error::{Error, Result};
 
 pub mod add;
+pub mod anomaly;
 pub mod data_type;
 pub mod display;
 pub mod div;
+pub mod higher_order;
 pub mod mul;
 pub mod others;
+pub mod stats;
 pub mod sub;
 
 /*
@@ -54,6 +63,11 @@ Use case:
       output in two ways. The first way is through the addition with B and the second way
       is through the multiplication with C.
 
+    - Only the Values that require the gradient get one. The constants created by the operations
+      with plain numbers(like `a + 2.0`) don't, and `set_requires_grad(false)` turns any Value into
+      a constant. An operation requires the gradient if any of its operands does, so backward
+      skips the whole subtrees made of constants.
+
 
 
 The chain rule of derivation can be explained as follows:
@@ -79,8 +93,8 @@ dC/dA = (C2 - C1)/((A+h)-A) = (((A+h)+B) - (A+B)) / h = (A+h+B-A-B)/h = 1
 - Similarly we can derive the gradient of multiplication and other operators also.
 */
 
-#[derive(Debug)]
-enum Operator {
+#[derive(Debug, Clone, Copy)]
+pub(crate) enum Operator {
     None,
     Plus,
     Minus,
@@ -89,18 +103,36 @@ enum Operator {
     Tanh,
     ReLU,
     Exp,
+    Log,
     Pow,
+    StopGradient,
 }
 
-pub struct Value {
-    data: DataType,
-    grad: DataType, // grad(global gradient) field will have gradient of final output with respect to the Value(Self).
-    operands: Vec<MVal>,
+pub struct Value<F: Float = DataType> {
+    data: F,
+    grad: F, // grad(global gradient) field will have gradient of final output with respect to the Value(Self).
+    operands: Vec<MVal<F>>,
     operator: Operator,
-    // for debugging purpose
+    // false for the constants and for the operations without any operand requiring the gradient.
+    // backward doesn't compute the gradient of such Values.
+    requires_grad: bool,
+    // true after backward with retain_graph false dropped the operands of the operation.
+    freed: bool,
+    // for debugging purpose. only the leaves created with new_lab have a label,
+    // labels of the operations are rendered from their operands when needed.
     label: String,
 }
 
+#[derive(Debug, Clone, Default)]
+pub struct BackwardOptions {
+    /// false(default) drops the operands of each operation after its gradient is computed. It
+    /// frees the memory of the graph as soon as possible, but backward can't be called on it again.
+    /// true keeps the graph, so each backward on it adds the same gradients again.
+    /// Only the operations the gradient passes through are freed, so the graphs under the
+    /// stop_gradient nodes and the constants are left as they are.
+    pub retain_graph: bool,
+}
+
 /// If you apply any of the +, -, *, / operation on the MVal, then it will return the new instance of MVal with the result.
 /// Remember that assignment operator will make the Mval instance to point to the new address.
 /// we need Rc, because we need to allow reuse of instance of MVal in multiple places.
@@ -108,59 +140,124 @@ pub struct Value {
 /// Cloning of MVal(MutableValue) is a cheap operation since it is acutally wrapper around Rc and RefCell
 /// because of the same if you try to use the same Value in two places, then
 /// it's gradient will be the accumulated sum of gradients of all the places it has been used.
+/// MVal is the same as MVal<f64>. See the Float trait for using other types like f32.
 #[derive(Clone)]
-pub struct MVal(Rc<RefCell<Value>>);
+pub struct MVal<F: Float = DataType>(Rc<RefCell<Value<F>>>);
 
 impl MVal {
     pub fn new<T: IntoValue>(data: T) -> Self {
-        Self(Rc::new(RefCell::new(Value::new(data))))
+        Self::new_typed(data)
     }
     pub fn new_lab<T: IntoValue>(data: T, label: &str) -> Self {
-        Self(Rc::new(RefCell::new(Value::new_lab(data, label))))
-    }
-    pub fn default() -> Self {
-        Self(Rc::new(RefCell::new(Value::new(0))))
+        Self::new_typed_lab(data, label)
     }
+}
 
-    pub fn grad(&self) -> DataType {
+impl<F: Float> MVal<F> {
+    /// Same as new but for any Float type. The type is usually given with MVal::<f32>::new_typed
+    pub fn new_typed<T: IntoValue<F>>(data: T) -> Self {
+        Self(Rc::new(RefCell::new(Value::new(data))))
+    }
+    pub fn new_typed_lab<T: IntoValue<F>>(data: T, label: &str) -> Self {
+        Self(Rc::new(RefCell::new(Value::new_lab(data, label))))
+    }
+    pub fn grad(&self) -> F {
         self.0.borrow().grad
     }
     /// to mutate the gradient inplace instead creating new instance.
-    pub fn set_grad(&self, val: MVal) {
+    pub fn set_grad(&self, val: MVal<F>) {
         self.0.borrow_mut().grad = val.grad();
     }
 
-    pub fn get(&self) -> DataType {
+    pub fn get(&self) -> F {
         self.0.borrow().data
     }
     /// to mutate the data inplace instead creating new instance.
-    pub fn set(&self, val: MVal) {
+    pub fn set(&self, val: MVal<F>) {
         self.0.borrow_mut().data = val.get();
     }
+
+    /// whether backward computes the gradient of the Value.
+    pub fn requires_grad(&self) -> bool {
+        self.0.borrow().requires_grad
+    }
+    /// false marks the Value as a constant(or freezes a parameter), so backward won't compute
+    /// its gradient. Only the operations created after the call will see the change.
+    pub fn set_requires_grad(&self, requires_grad: bool) {
+        self.0.borrow_mut().requires_grad = requires_grad;
+    }
+
+    /// creates the Value of an operation. It requires the gradient if any of its operands does.
+    pub(crate) fn from_op(data: F, operands: Vec<MVal<F>>, operator: Operator) -> Self {
+        let requires_grad = operands.iter().any(|op| op.requires_grad());
+        let val = Value {
+            data,
+            grad: F::zero(),
+            operands,
+            operator,
+            requires_grad,
+            freed: false,
+            label: String::new(),
+        };
+        val.check_forward();
+        Self(Rc::new(RefCell::new(val)))
+    }
+
+    /// creates the Value of the constants used in the operations with plain numbers like MVal + 2.0
+    pub(crate) fn constant(data: F) -> Self {
+        let val = Self::new_typed(data);
+        val.set_requires_grad(false);
+        val
+    }
 }
 
-impl Value {
+impl<F: Float> Default for MVal<F> {
+    fn default() -> Self {
+        Self(Rc::new(RefCell::new(Value::new(F::zero()))))
+    }
+}
+
+impl<F: Float> Value<F> {
     /// Cloning of returned value(MVal) is a cheap operation since it is acutally wrapper around Rc and RefCell
-    pub fn new<T: IntoValue>(data: T) -> Self {
+    pub fn new<T: IntoValue<F>>(data: T) -> Self {
         Self {
             data: data.into_value(),
-            grad: 0.0,
+            grad: F::zero(),
             operands: vec![],
             operator: Operator::None,
+            requires_grad: true,
+            freed: false,
             label: String::new(),
         }
     }
     /// new with label is for the debugging process that assigns label for each Value.
-    pub fn new_lab<T: IntoValue>(data: T, label: &str) -> Self {
+    pub fn new_lab<T: IntoValue<F>>(data: T, label: &str) -> Self {
         Self {
             data: data.into_value(),
-            grad: 0.0,
+            grad: F::zero(),
             operands: vec![],
             operator: Operator::None,
+            requires_grad: true,
+            freed: false,
             label: label.to_string(),
         }
     }
 
+    /// drops the operands of an operation after backward. Leaves have nothing to free.
+    fn free(&mut self) {
+        if !matches!(self.operator, Operator::None) {
+            self.operands = vec![];
+            self.freed = true;
+        }
+    }
+
+    /// adds the grad to the gradient of the Value unless it doesn't require the gradient.
+    fn accumulate(&mut self, grad: F) {
+        if self.requires_grad {
+            self.grad += grad;
+        }
+    }
+
     /// calling compute gradient on a output value will comput the gradient value
     /// w.r.t each one of its operands and store those gradients in the corresponding operands objects.
     /// Here chain rule of derivative is used: dy/dz = (dy/dx)(dx/dz)
@@ -182,8 +279,8 @@ impl Value {
                     */
                     if let Ok(mut rhs) = out.operands[1].0.try_borrow_mut() {
                         // if y = x + z, then dy/dx = 1 and dy/dz = 1
-                        lhs.grad += out.grad * 1.0;
-                        rhs.grad += out.grad * 1.0;
+                        lhs.accumulate(out.grad);
+                        rhs.accumulate(out.grad);
                     } else {
                         /*
                             since this is single threaded only other place we got the
@@ -191,7 +288,7 @@ impl Value {
 
                             if y = x + x = 2x then dy/dx = 2;
                         */
-                        lhs.grad += out.grad * 2.0;
+                        lhs.accumulate(out.grad + out.grad);
                     }
                 }
             }
@@ -201,8 +298,8 @@ impl Value {
                     // handle: let c = a.clone() - a;
                     if let Ok(mut rhs) = out.operands[1].0.try_borrow_mut() {
                         // if y = x - z, then dy/dx = 1 and dy/dz = -1
-                        lhs.grad += out.grad * 1.0;
-                        rhs.grad += out.grad * -1.0;
+                        lhs.accumulate(out.grad);
+                        rhs.accumulate(-out.grad);
                     }
                     // else case: if y = x - x, then dy/dx = 0
                 }
@@ -211,11 +308,12 @@ impl Value {
                 if out.operands.len() == 2 {
                     let mut lhs = out.operands[0].0.borrow_mut();
                     if let Ok(mut rhs) = out.operands[1].0.try_borrow_mut() {
-                        lhs.grad += out.grad * rhs.data;
-                        rhs.grad += out.grad * lhs.data;
+                        lhs.accumulate(out.grad * rhs.data);
+                        rhs.accumulate(out.grad * lhs.data);
                     } else {
                         // if y = x * x = x^2, then dy/dx = 2x
-                        lhs.grad += out.grad * (2.0 * lhs.data);
+                        let grad = out.grad * (lhs.data + lhs.data);
+                        lhs.accumulate(grad);
                     }
                 }
             }
@@ -225,9 +323,10 @@ impl Value {
 
                     // y=x/z then dy/dx = 1/z and dy/dz = -x/z^2.
                     if let Ok(mut denominator) = out.operands[1].0.try_borrow_mut() {
-                        numerator.grad += out.grad * 1.0 / denominator.data;
-                        denominator.grad += (out.grad * -1.0 * numerator.data)
-                            / (denominator.data * denominator.data);
+                        numerator.accumulate(out.grad / denominator.data);
+                        let grad =
+                            (-out.grad * numerator.data) / (denominator.data * denominator.data);
+                        denominator.accumulate(grad);
                     }
                     // else case: if y = x/x, then dy/dx = 0
                 }
@@ -237,7 +336,7 @@ impl Value {
                     let mut input = out.operands[0].0.borrow_mut();
 
                     // y = tahh(x) then dy/dx = 1 - (tanh(x))^2
-                    input.grad += out.grad * (1.0 - (out.data * out.data));
+                    input.accumulate(out.grad * (F::one() - (out.data * out.data)));
                 }
             }
             Operator::ReLU => {
@@ -245,7 +344,14 @@ impl Value {
                     let mut input = out.operands[0].0.borrow_mut();
 
                     // ReLU y = max(0,x). dy/dx = 1 for x > 0.
-                    input.grad += out.grad * (if out.data > 0.0 { 1.0 } else { 0.0 });
+                    input.accumulate(
+                        out.grad
+                            * (if out.data > F::zero() {
+                                F::one()
+                            } else {
+                                F::zero()
+                            }),
+                    );
                 }
             }
             Operator::Exp => {
@@ -253,7 +359,16 @@ impl Value {
                     let mut input = out.operands[0].0.borrow_mut();
 
                     // y = exp(x) then dy/dx = exp(x)
-                    input.grad += out.grad * out.data;
+                    input.accumulate(out.grad * out.data);
+                }
+            }
+            Operator::Log => {
+                if out.operands.len() == 1 {
+                    let mut input = out.operands[0].0.borrow_mut();
+
+                    // y = ln(x) then dy/dx = 1/x
+                    let grad = out.grad / input.data;
+                    input.accumulate(grad);
                 }
             }
             Operator::Pow => {
@@ -262,58 +377,92 @@ impl Value {
                     let powv = out.operands[1].0.borrow().data;
 
                     // y = x^n then dy/dx = n * x^(n-1)
-                    input.grad += out.grad * (powv * input.data.powf(powv - 1.0));
+                    let grad = out.grad * (powv * input.data.powf(powv - F::one()));
+                    input.accumulate(grad);
                 }
             }
+            // the gradient is not passed to the operand.
+            Operator::StopGradient => {}
             Operator::None => {}
         }
     }
 }
 
-impl MVal {
-    /// update the gradient values inplace.
-    pub fn backward_debug(&mut self) {
-        let mut all_nodes = self.collect_operands();
-        all_nodes.reverse();
+impl<F: Float> MVal<F> {
+    /// Same as backward but prints each node after computing the gradients of its operands.
+    pub fn backward_debug(&mut self) -> Result<()> {
+        let all_nodes = self.backward_nodes()?;
 
         // set the gradient of the root node to 1 since gradient with itself is 1.
-        if let Some(first) = all_nodes.get(0) {
-            let mut val = first.0.borrow_mut();
-            val.grad = 1.0;
-
-            val.comput_gradient();
-            println!("{:?}", val)
+        if let Some(first) = all_nodes.first() {
+            first.0.borrow_mut().grad = F::one();
         }
-        for i in 1..all_nodes.len() {
-            if let Some(el) = all_nodes.get(i) {
-                let mut val = el.0.borrow_mut();
-                val.comput_gradient();
-                println!("{:?}", val)
-            }
+        for node in &all_nodes {
+            let mut val = node.0.borrow_mut();
+            val.comput_gradient();
+            println!("{:?}", val);
+            val.free();
         }
+        self.0.borrow_mut().free();
+        Ok(())
     }
 
     /// update the gradient values inplace.
-    pub fn backward(&mut self) {
-        let mut all_nodes = self.collect_operands();
-        all_nodes.reverse();
+    /// Values that don't require the gradient and the subtrees under them are skipped.
+    /// Like PyTorch the graph is freed, so calling it again returns GraphFreed instead of adding
+    /// the same gradients again. Use backward_custom with retain_graph to keep the graph.
+    pub fn backward(&mut self) -> Result<()> {
+        self.backward_custom(&BackwardOptions::default())
+    }
 
-        // set the gradient of the root node to 1 since gradient with itself is 1.
-        if let Some(first) = all_nodes.get(0) {
-            let mut val = first.0.borrow_mut();
-            val.grad = 1.0;
+    /// Same as backward but with custom options.
+    /// In the anomaly detection mode it stops at the first node that produced a non-finite
+    /// gradient and returns it as error.
+    /// returns error if any part of the graph was already freed by a previous backward,
+    /// including the graph shared with an other Value that backward was called on.
+    pub fn backward_custom(&mut self, options: &BackwardOptions) -> Result<()> {
+        let all_nodes = self.backward_nodes()?;
+        let detecting = anomaly::is_detecting();
 
-            val.comput_gradient();
+        // set the gradient of the root node to 1 since gradient with itself is 1.
+        if let Some(first) = all_nodes.first() {
+            first.0.borrow_mut().grad = F::one();
         }
-        for i in 1..all_nodes.len() {
-            if let Some(el) = all_nodes.get(i) {
-                let mut val = el.0.borrow_mut();
-                val.comput_gradient();
+        for node in &all_nodes {
+            let mut val = node.0.borrow_mut();
+            val.comput_gradient();
+            if detecting {
+                val.check_backward()?;
+            }
+            if !options.retain_graph {
+                // the operands are not needed anymore, dropping them frees the
+                // intermediate Values that nothing else is referring to.
+                val.free();
             }
         }
+        if !options.retain_graph {
+            self.0.borrow_mut().free();
+        }
+        Ok(())
+    }
+
+    /// true for the operations whose operands were dropped by backward with retain_graph false.
+    pub fn is_freed(&self) -> bool {
+        self.0.borrow().freed
+    }
+
+    /// nodes of the backward pass in the reverse topological order(the calling Value first).
+    /// returns error if any of them was freed, since the gradient can't pass through it anymore.
+    fn backward_nodes(&self) -> Result<Vec<MVal<F>>> {
+        let mut all_nodes = self.collect_grad_nodes();
+        if self.is_freed() || all_nodes.iter().any(|node| node.is_freed()) {
+            return Err(Error::GraphFreed);
+        }
+        all_nodes.reverse();
+        Ok(all_nodes)
     }
 
-    pub fn collect_operands(&self) -> Vec<MVal> {
+    pub fn collect_operands(&self) -> Vec<MVal<F>> {
         let mut visited = HashSet::new();
         self.collect_operands_inner(&mut visited)
     }
@@ -326,7 +475,7 @@ impl MVal {
       called twice. To avoide this issue we use topological order and check weather each node is
       visited before exploring all its children.
     */
-    pub fn collect_operands_inner(&self, visited: &mut HashSet<MVal>) -> Vec<MVal> {
+    pub fn collect_operands_inner(&self, visited: &mut HashSet<MVal<F>>) -> Vec<MVal<F>> {
         let mut all_nodes = vec![];
 
         // if not already visited, then collect all of its children.
@@ -342,6 +491,31 @@ impl MVal {
         all_nodes
     }
 
+    /// same as collect_operands but without the Values that don't require the gradient.
+    /// Since none of the operands of such a Value requires the gradient either,
+    /// the whole subtree under it is skipped.
+    fn collect_grad_nodes(&self) -> Vec<MVal<F>> {
+        let mut visited = HashSet::new();
+        let mut all_nodes = vec![];
+        self.collect_grad_nodes_inner(&mut visited, &mut all_nodes);
+        all_nodes
+    }
+
+    fn collect_grad_nodes_inner(
+        &self,
+        visited: &mut HashSet<MVal<F>>,
+        all_nodes: &mut Vec<MVal<F>>,
+    ) {
+        if !self.requires_grad() || visited.contains(self) {
+            return;
+        }
+        visited.insert(self.clone());
+        for op in self.0.borrow().operands.iter() {
+            op.collect_grad_nodes_inner(visited, all_nodes);
+        }
+        all_nodes.push(self.clone());
+    }
+
     /// set the gradient value of all its
     /// children(all Values those which a calling Value is depending on) to 0.
     pub fn zero_grad(&self) {
@@ -350,10 +524,10 @@ impl MVal {
     }
 
     /// set all the gradient values to zero
-    pub fn zero_grad_inner(&self, visited: &mut HashSet<MVal>) {
+    pub fn zero_grad_inner(&self, visited: &mut HashSet<MVal<F>>) {
         // if not already visited, then collect all of its children.
         let mut val = self.0.borrow_mut();
-        val.grad = 0.0;
+        val.grad = F::zero();
         if !visited.contains(self) {
             visited.insert(self.clone());
             val.operands.iter().for_each(|op| {
@@ -364,7 +538,7 @@ impl MVal {
 }
 
 /// Implement Hash based on the address of the Rc
-impl Hash for MVal {
+impl<F: Float> Hash for MVal<F> {
     fn hash<H: Hasher>(&self, state: &mut H) {
         let ptr = Rc::as_ptr(&self.0) as *const (); // Get raw pointer address
         ptr.hash(state);
@@ -372,10 +546,10 @@ impl Hash for MVal {
 }
 
 /// Implement Eq and PartialEq based on the address of the Rc
-impl PartialEq for MVal {
+impl<F: Float> PartialEq for MVal<F> {
     fn eq(&self, other: &Self) -> bool {
         Rc::as_ptr(&self.0) == Rc::as_ptr(&other.0)
     }
 }
 
-impl Eq for MVal {}
+impl<F: Float> Eq for MVal<F> {}
@@ -1,78 +1,59 @@
 use super::{
-    data_type::{DataType, IntoValue},
-    MVal, Operator, Value,
+    data_type::{Float, IntoValue},
+    MVal, Operator,
 };
-use std::{cell::RefCell, ops::Mul, rc::Rc};
+use std::ops::Mul;
 
 // MVal * MVal
-impl Mul for MVal {
-    type Output = MVal;
+impl<F: Float> Mul for MVal<F> {
+    type Output = MVal<F>;
 
     fn mul(self, rhs: Self) -> Self::Output {
         let lhsv = self.0.borrow();
         let rhsv = rhs.0.borrow();
 
-        MVal(Rc::new(RefCell::new(Value {
-            data: lhsv.data * rhsv.data,
-            grad: 0.0,
-            operands: vec![self.clone(), rhs.clone()],
-            operator: Operator::Mul,
-            label: format!("({}*{})", lhsv.label, rhsv.label),
-        })))
+        MVal::from_op(
+            lhsv.data * rhsv.data,
+            vec![self.clone(), rhs.clone()],
+            Operator::Mul,
+        )
     }
 }
 
-// DataType * MVal
-impl Mul<MVal> for DataType {
-    type Output = MVal;
-
-    fn mul(self, rhs: MVal) -> Self::Output {
-        let lhsv = self;
-        let lhs = MVal(Rc::new(RefCell::new(Value {
-            data: lhsv,
-            grad: 0.0,
-            operands: vec![],
-            operator: Operator::None,
-            label: String::new(),
-        })));
-
-        let rhsv = rhs.0.borrow();
-
-        MVal(Rc::new(RefCell::new(Value {
-            data: lhsv * rhsv.data,
-            grad: 0.0,
-            operands: vec![lhs, rhs.clone()],
-            operator: Operator::Mul,
-            label: format!("({}*{})", lhsv, rhsv.label),
-        })))
-    }
+macro_rules! impl_mul {
+    ($t:ty) => {
+        // DataType * MVal
+        impl Mul<MVal<$t>> for $t {
+            type Output = MVal<$t>;
+
+            fn mul(self, rhs: MVal<$t>) -> Self::Output {
+                let lhsv = self;
+                let lhs = MVal::constant(lhsv);
+
+                let rhsv = rhs.0.borrow();
+
+                MVal::from_op(lhsv * rhsv.data, vec![lhs, rhs.clone()], Operator::Mul)
+            }
+        }
+
+        // MVal * T
+        impl<T> Mul<T> for MVal<$t>
+        where
+            T: IntoValue<$t>,
+        {
+            type Output = MVal<$t>;
+
+            fn mul(self, rhs: T) -> Self::Output {
+                let lhs = self;
+                let lhsv = lhs.0.borrow();
+
+                let rhsv = rhs.into_value();
+                let rhs = MVal::constant(rhsv);
+                MVal::from_op(lhsv.data * rhsv, vec![lhs.clone(), rhs], Operator::Mul)
+            }
+        }
+    };
 }
 
-// MVal * T
-impl<T> Mul<T> for MVal
-where
-    T: IntoValue,
-{
-    type Output = MVal;
-
-    fn mul(self, rhs: T) -> Self::Output {
-        let lhs = self;
-        let lhsv = lhs.0.borrow();
-
-        let rhsv = rhs.into_value();
-        let rhs = MVal(Rc::new(RefCell::new(Value {
-            data: rhsv,
-            grad: 0.0,
-            operands: vec![],
-            operator: Operator::None,
-            label: String::new(),
-        })));
-        MVal(Rc::new(RefCell::new(Value {
-            data: lhsv.data * rhsv,
-            grad: 0.0,
-            operands: vec![lhs.clone(), rhs],
-            operator: Operator::Mul,
-            label: format!("({}*{})", lhsv.label, rhsv),
-        })))
-    }
-}
+impl_mul!(f32);
+impl_mul!(f64);
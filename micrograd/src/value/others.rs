@@ -1,59 +1,63 @@
-use super::{data_type::IntoValue, MVal, Operator, Value};
-use std::{cell::RefCell, rc::Rc};
+use super::{
+    data_type::{Float, IntoValue},
+    MVal, Operator,
+};
 
-impl MVal {
-    pub fn tanh(self) -> MVal {
+impl<F: Float> MVal<F> {
+    pub fn tanh(self) -> MVal<F> {
         let val = self.0.borrow().data;
-        MVal(Rc::new(RefCell::new(Value {
-            data: val.tanh(),
-            grad: 0.0,
-            operands: vec![self.clone()],
-            operator: Operator::Tanh,
-            label: format!("tanh({})", val),
-        })))
+        MVal::from_op(val.tanh(), vec![self.clone()], Operator::Tanh)
     }
 
     // Rectified Linear Unit y = max(0,x)
-    pub fn relu(self) -> MVal {
+    pub fn relu(self) -> MVal<F> {
         let val = self.0.borrow().data;
-        MVal(Rc::new(RefCell::new(Value {
-            data: if val < 0.0 { 0.0 } else { val },
-            grad: 0.0,
-            operands: vec![self.clone()],
-            operator: Operator::ReLU,
-            label: format!("ReLU({})", val),
-        })))
+        MVal::from_op(
+            if val < F::zero() { F::zero() } else { val },
+            vec![self.clone()],
+            Operator::ReLU,
+        )
     }
 
-    pub fn exp(self) -> MVal {
+    pub fn exp(self) -> MVal<F> {
         let val = self.0.borrow().data;
-        MVal(Rc::new(RefCell::new(Value {
-            data: val.exp(),
-            grad: 0.0,
-            operands: vec![self.clone()],
-            operator: Operator::Exp,
-            label: format!("exp({})", val),
-        })))
+        MVal::from_op(val.exp(), vec![self.clone()], Operator::Exp)
     }
 
-    pub fn pow<T: IntoValue>(self, other: T) -> MVal {
+    /// natural logarithm. The data should be positive.
+    pub fn log(self) -> MVal<F> {
+        let val = self.0.borrow().data;
+        MVal::from_op(val.ln(), vec![self.clone()], Operator::Log)
+    }
+
+    pub fn pow<T: IntoValue<F>>(self, other: T) -> MVal<F> {
         let val = self.0.borrow().data;
 
         let powv = other.into_value();
-        let power = MVal(Rc::new(RefCell::new(Value {
-            data: powv,
-            grad: 0.0,
-            operands: vec![],
-            operator: Operator::None,
-            label: String::new(),
-        })));
-
-        MVal(Rc::new(RefCell::new(Value {
-            data: val.powf(powv),
-            grad: 0.0,
-            operands: vec![self.clone(), power],
-            operator: Operator::Pow,
-            label: format!("pow({},{})", val, powv),
-        })))
+        let power = MVal::constant(powv);
+
+        MVal::from_op(val.powf(powv), vec![self.clone(), power], Operator::Pow)
+    }
+
+    /// returns a new leaf with the same data that doesn't require the gradient. The graph of the
+    /// calling Value is not part of the new Value, so backward never reaches it through the copy.
+    /// Used to take the output of a target network as a constant.
+    pub fn detach(&self) -> MVal<F> {
+        MVal::constant(self.get())
+    }
+
+    /// passes the data through, but blocks the gradient. Unlike detach, the Value stays in
+    /// the graph(for display and graph_stats) as the operand of the returned node.
+    /// For example the straight-through estimator of rounding takes the data of the rounded
+    /// Value and the gradient of the identity.
+    ///         `let y = x.clone() + (rounded - x.clone()).stop_gradient();`
+    /// backward never walks below the returned node, so backward with retain_graph false
+    /// doesn't free the graph of the calling Value. Other Values can still backward through it.
+    pub fn stop_gradient(self) -> MVal<F> {
+        let val = self.0.borrow().data;
+        let out = MVal::from_op(val, vec![self.clone()], Operator::StopGradient);
+        // backward skips the Values that don't require the gradient and the subtrees under them.
+        out.set_requires_grad(false);
+        out
     }
 }
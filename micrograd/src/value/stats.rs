@@ -0,0 +1,94 @@
+use std::{
+    cell::RefCell,
+    collections::{BTreeMap, HashMap},
+    fmt::Display,
+    mem::size_of,
+};
+
+use super::{data_type::Float, MVal, Operator, Value};
+
+/*
+What is in the GraphStats?
+    - The size of the graph that a Value is depending on. Every operation creates a new node, so
+      the graph of the loss of a batch has a node for each addition, multiplication and activation
+      of every Neuron for every sample of the batch.
+
+    - depth is the number of nodes on the longest path from the Value to a leaf. backward and
+      zero_grad recurse once per node on that path.
+
+    - memory is an estimate of the heap memory held by the graph. It is the size of the Rc
+      allocation of each node plus the operands list and the label of it.
+*/
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct GraphStats {
+    /// number of unique Values in the graph including the leaves.
+    pub nodes: usize,
+    /// Values without operands, the parameters, inputs and constants.
+    pub leaves: usize,
+    /// leaves that don't require the gradient.
+    pub constants: usize,
+    /// number of nodes of each operation like Plus or Tanh. Leaves are not included.
+    pub operators: BTreeMap<String, usize>,
+    pub depth: usize,
+    /// estimated memory of the graph in bytes.
+    pub memory: usize,
+}
+
+impl<F: Float> MVal<F> {
+    /// collects the statistics of the graph the calling Value is depending on.
+    pub fn graph_stats(&self) -> GraphStats {
+        let mut stats = GraphStats::default();
+        // depth of each node. Operands come before the operations in the topological order.
+        let mut depths: HashMap<MVal<F>, usize> = HashMap::new();
+        for node in self.collect_operands() {
+            let val = node.0.borrow();
+            stats.nodes += 1;
+            // Rc allocation has the strong and weak counts before the RefCell.
+            stats.memory += 2 * size_of::<usize>()
+                + size_of::<RefCell<Value<F>>>()
+                + val.operands.capacity() * size_of::<MVal<F>>()
+                + val.label.capacity();
+            match val.operator {
+                Operator::None => {
+                    stats.leaves += 1;
+                    if !val.requires_grad {
+                        stats.constants += 1;
+                    }
+                }
+                op => *stats.operators.entry(format!("{op:?}")).or_default() += 1,
+            }
+            let depth = 1 + val
+                .operands
+                .iter()
+                .filter_map(|op| depths.get(op))
+                .max()
+                .copied()
+                .unwrap_or(0);
+            drop(val);
+            depths.insert(node, depth);
+        }
+        stats.depth = depths.get(self).copied().unwrap_or(0);
+        stats
+    }
+}
+
+impl Display for GraphStats {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(
+            f,
+            "nodes:{}, leaves:{}, constants:{}, depth:{}, memory:{:.1}KiB",
+            self.nodes,
+            self.leaves,
+            self.constants,
+            self.depth,
+            self.memory as f64 / 1024.0
+        )?;
+        let operators: Vec<String> = self
+            .operators
+            .iter()
+            .map(|(op, count)| format!("{op}:{count}"))
+            .collect();
+        write!(f, "operators: {}", operators.join(", "))
+    }
+}
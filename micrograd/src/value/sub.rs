@@ -1,79 +1,60 @@
 use super::{
-    data_type::{DataType, IntoValue},
-    MVal, Operator, Value,
+    data_type::{Float, IntoValue},
+    MVal, Operator,
 };
-use std::{cell::RefCell, ops::Sub, rc::Rc};
+use std::ops::Sub;
 
 // MVal - MVal
-impl Sub for MVal {
-    type Output = MVal;
+impl<F: Float> Sub for MVal<F> {
+    type Output = MVal<F>;
 
     fn sub(self, rhs: Self) -> Self::Output {
         let lhsv = self.0.borrow();
         let rhsv = rhs.0.borrow();
 
-        MVal(Rc::new(RefCell::new(Value {
-            data: lhsv.data - rhsv.data,
-            grad: 0.0,
-            operands: vec![self.clone(), rhs.clone()],
-            operator: Operator::Minus,
-            label: format!("({}-{})", lhsv.label, rhsv.label),
-        })))
+        MVal::from_op(
+            lhsv.data - rhsv.data,
+            vec![self.clone(), rhs.clone()],
+            Operator::Minus,
+        )
     }
 }
 
-// DataType - MVal
-impl Sub<MVal> for DataType {
-    type Output = MVal;
+macro_rules! impl_sub {
+    ($t:ty) => {
+        // DataType - MVal
+        impl Sub<MVal<$t>> for $t {
+            type Output = MVal<$t>;
 
-    fn sub(self, rhs: MVal) -> Self::Output {
-        let lhsv = self;
-        let lhs = MVal(Rc::new(RefCell::new(Value {
-            data: lhsv,
-            grad: 0.0,
-            operands: vec![],
-            operator: Operator::None,
-            label: String::new(),
-        })));
+            fn sub(self, rhs: MVal<$t>) -> Self::Output {
+                let lhsv = self;
+                let lhs = MVal::constant(lhsv);
 
-        let rhsv = rhs.0.borrow();
+                let rhsv = rhs.0.borrow();
 
-        MVal(Rc::new(RefCell::new(Value {
-            data: lhsv - rhsv.data,
-            grad: 0.0,
-            operands: vec![lhs, rhs.clone()],
-            operator: Operator::Minus,
-            label: format!("({}-{})", lhsv, rhsv.label),
-        })))
-    }
-}
+                MVal::from_op(lhsv - rhsv.data, vec![lhs, rhs.clone()], Operator::Minus)
+            }
+        }
 
-// MVal - T
-impl<T> Sub<T> for MVal
-where
-    T: IntoValue,
-{
-    type Output = MVal;
+        // MVal - T
+        impl<T> Sub<T> for MVal<$t>
+        where
+            T: IntoValue<$t>,
+        {
+            type Output = MVal<$t>;
 
-    fn sub(self, rhs: T) -> Self::Output {
-        let lhs = self;
-        let lhsv = lhs.0.borrow();
+            fn sub(self, rhs: T) -> Self::Output {
+                let lhs = self;
+                let lhsv = lhs.0.borrow();
 
-        let rhsv = rhs.into_value();
-        let rhs = MVal(Rc::new(RefCell::new(Value {
-            data: rhsv,
-            grad: 0.0,
-            operands: vec![],
-            operator: Operator::None,
-            label: String::new(),
-        })));
+                let rhsv = rhs.into_value();
+                let rhs = MVal::constant(rhsv);
 
-        MVal(Rc::new(RefCell::new(Value {
-            data: lhsv.data - rhsv,
-            grad: 0.0,
-            operands: vec![lhs.clone(), rhs],
-            operator: Operator::Minus,
-            label: format!("({}-{})", lhsv.label, rhsv),
-        })))
-    }
+                MVal::from_op(lhsv.data - rhsv, vec![lhs.clone(), rhs], Operator::Minus)
+            }
+        }
+    };
 }
+
+impl_sub!(f32);
+impl_sub!(f64);